@@ -0,0 +1,69 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rust_state_machine::automats::DAS::DAS;
+use rust_state_machine::elements::Alphabet::Alphabet;
+use rust_state_machine::elements::Node::DASNode;
+use std::hint::black_box;
+
+const PATTERN: &str = "ERROR";
+
+// DAS akceptujący linie logu zawierające `PATTERN` (automat KMP nad
+// drukowalnymi znakami ASCII i znakiem nowej linii)
+fn contains_pattern() -> DAS {
+    let pattern: Vec<char> = PATTERN.chars().collect();
+    let mut alphabet = Alphabet::new();
+    alphabet.add('\n');
+    for c in ' '..='~' {
+        alphabet.add(c);
+    }
+    let mut das = DAS::new(alphabet.clone());
+    for matched in 0..=pattern.len() {
+        let mut node = DASNode::new(&format!("q{}", matched), matched == pattern.len());
+        for c in alphabet.iter() {
+            let next = if matched == pattern.len() {
+                matched
+            } else {
+                // Najdłuższy prefiks wzorca będący sufiksem przeczytanego tekstu
+                let mut text: Vec<char> = pattern[..matched].to_vec();
+                text.push(*c);
+                (0..=text.len().min(pattern.len()))
+                    .rev()
+                    .find(|&k| text[text.len() - k..] == pattern[..k])
+                    .unwrap()
+            };
+            node.add_connection(*c, &format!("q{}", next));
+        }
+        das.add_state(node);
+    }
+    das.set_start_state("q0");
+    das
+}
+
+// Log o rozmiarze `size` bajtów z jedną linią "ERROR" na samym końcu
+fn log(size: usize) -> String {
+    let line = "2024-05-01 12:00:00 INFO request handled in 12 ms, status=200\n";
+    let mut log = line.repeat(size / line.len());
+    log.push_str("2024-05-01 12:00:01 ERROR connection reset\n");
+    log
+}
+
+fn bench(c: &mut Criterion) {
+    let das = contains_pattern();
+    let compiled = das.compile();
+    let mut group = c.benchmark_group("contains_error");
+    group.sample_size(10);
+    for size in [1 << 20, 4 << 20] {
+        let input = log(size);
+        assert!(das.process(&input) && compiled.matches(input.as_bytes()));
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("DAS::process", size), &input, |b, input| {
+            b.iter(|| das.process(black_box(input)))
+        });
+        group.bench_with_input(BenchmarkId::new("CompiledDas::matches", size), &input, |b, input| {
+            b.iter(|| compiled.matches(black_box(input.as_bytes())))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
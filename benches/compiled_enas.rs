@@ -0,0 +1,84 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rust_state_machine::automats::ENAS::ENAS;
+use rust_state_machine::elements::Alphabet::Alphabet;
+use rust_state_machine::elements::Label::Label;
+use rust_state_machine::elements::Node::ENASNode;
+use std::hint::black_box;
+
+const PATTERNS: [&str; 3] = ["ERROR", "WARN", "timeout"];
+
+// ε-NAS akceptujący linie logu zawierające którykolwiek ze wzorców: stan
+// startowy czyta dowolny znak, a przejściami ε wchodzi w gałąź każdego wzorca
+fn contains_any() -> ENAS {
+    let mut alphabet = Alphabet::new();
+    alphabet.add('\n');
+    for c in ' '..='~' {
+        alphabet.add(c);
+    }
+    let any = |node: &mut ENASNode, target: &str| {
+        for c in alphabet.iter() {
+            node.add_connection(Label::Symbol(*c), vec![target.to_string()]);
+        }
+    };
+
+    let mut enas = ENAS::new(alphabet.clone());
+    let mut start = ENASNode::new("start", false);
+    any(&mut start, "start");
+    start.add_connection(Label::Epsilon, PATTERNS.iter().map(|p| format!("{}0", p)).collect());
+    enas.add_state(start);
+    for pattern in PATTERNS {
+        for (i, c) in pattern.chars().enumerate() {
+            let mut node = ENASNode::new(&format!("{}{}", pattern, i), false);
+            node.add_connection(Label::Symbol(c), vec![format!("{}{}", pattern, i + 1)]);
+            enas.add_state(node);
+        }
+        let mut end = ENASNode::new(&format!("{}{}", pattern, pattern.len()), false);
+        end.add_connection(Label::Epsilon, vec!["found".to_string()]);
+        enas.add_state(end);
+    }
+    let mut found = ENASNode::new("found", true);
+    any(&mut found, "found");
+    enas.add_state(found);
+    enas.set_start_state("start");
+    enas
+}
+
+// Log o rozmiarze `size` bajtów z jedną linią "ERROR" na samym końcu
+fn log(size: usize) -> String {
+    let line = "2024-05-01 12:00:00 INFO request handled in 12 ms, status=200\n";
+    let mut log = line.repeat(size / line.len());
+    log.push_str("2024-05-01 12:00:01 ERROR connection reset\n");
+    log
+}
+
+fn bench(c: &mut Criterion) {
+    let enas = contains_any();
+    let compiled = enas.compile();
+    let mut group = c.benchmark_group("contains_any");
+    group.sample_size(10);
+
+    // Symulacja na napisach jest zbyt wolna dla większych wejść
+    let input = log(64 << 10);
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_with_input(BenchmarkId::new("ENAS::process", input.len()), &input, |b, input| {
+        b.iter(|| enas.process(black_box(input)))
+    });
+
+    for size in [1 << 20, 4 << 20] {
+        let input = log(size);
+        assert!(compiled.matches(input.as_bytes()) && compiled.lazy_dfa().matches(input.as_bytes()));
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("CompiledEnas::matches", size), &input, |b, input| {
+            b.iter(|| compiled.matches(black_box(input.as_bytes())))
+        });
+        // Pamięć podręczna DFA jest współdzielona między iteracjami
+        let mut lazy = compiled.lazy_dfa();
+        group.bench_with_input(BenchmarkId::new("LazyDfa::matches", size), &input, |b, input| {
+            b.iter(|| lazy.matches(black_box(input.as_bytes())))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+// Zbiór stanów jako wektor bitów – stan `i` to bit `i % 64` słowa `i / 64`
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub(crate) fn new(size: usize) -> Self {
+        Self {
+            words: vec![0; size.div_ceil(64)],
+        }
+    }
+
+    pub(crate) fn insert(&mut self, i: usize) -> bool {
+        let (word, bit) = (i / 64, 1 << (i % 64));
+        let inserted = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        inserted
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub(crate) fn union_with(&mut self, other: &BitSet) {
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+
+    pub(crate) fn intersects(&self, other: &BitSet) -> bool {
+        self.words.iter().zip(&other.words).any(|(a, b)| a & b != 0)
+    }
+
+    // Numery stanów należących do zbioru, rosnąco
+    pub(crate) fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+}
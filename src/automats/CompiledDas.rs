@@ -0,0 +1,136 @@
+use crate::elements::Node::NodeBase;
+use crate::elements::Symbol::Symbol;
+use crate::automats::DAS::DAS;
+use std::collections::HashMap;
+
+// Stan martwy – zawsze wiersz 0 tabeli. Trafiają do niego brakujące przejścia,
+// symbole spoza alfabetu i przejścia do nieistniejących stanów.
+const DEAD: u32 = 0;
+
+// DAS skompilowany do gęstej tabeli przejść. Stany i symbole są numerowane,
+// a tabela przechowuje od razu początek wiersza stanu docelowego
+// (`stan * szerokość`), więc krok to jedno odczytanie z tablicy.
+#[derive(Clone)]
+pub struct CompiledDas<S: Symbol = char> {
+    // symbol → kolumna tabeli (kolumna 0 to symbole spoza alfabetu)
+    columns: HashMap<S, u32>,
+    // Kolumny dla bajtów wejścia – szybka ścieżka `matches`
+    byte_columns: [u32; 256],
+    width: u32,
+    table: Vec<u32>,
+    // Indeksowane numerem stanu (nie początkiem wiersza)
+    accepting: Vec<bool>,
+    start: u32,
+}
+
+impl<S: Symbol> DAS<S> {
+    pub fn compile(&self) -> CompiledDas<S> {
+        CompiledDas::new(self)
+    }
+}
+
+impl<S: Symbol> CompiledDas<S> {
+    pub fn new(das: &DAS<S>) -> Self {
+        let symbols = das.alphabet().symbols();
+        let width = symbols.len() as u32 + 1;
+        let columns: HashMap<S, u32> = symbols.iter().cloned().zip(1..).collect();
+        let mut byte_columns = [0; 256];
+        for (symbol, &column) in &columns {
+            if let Some(byte) = symbol.as_byte() {
+                byte_columns[byte as usize] = column;
+            }
+        }
+
+        let mut names: Vec<String> = das.get_states().keys().cloned().collect();
+        names.sort();
+        names.insert(0, String::new());
+        let ids: HashMap<&str, u32> = names.iter().enumerate().skip(1).map(|(i, n)| (n.as_str(), i as u32)).collect();
+
+        let mut table = vec![DEAD; names.len() * width as usize];
+        let mut accepting = vec![false; names.len()];
+        for (name, &id) in &ids {
+            let node = &das.get_states()[*name];
+            accepting[id as usize] = node.is_accepting();
+            for (symbol, target) in node.get_connections() {
+                if let (Some(&column), Some(&target)) = (columns.get(symbol), ids.get(target.as_str())) {
+                    table[(id * width + column) as usize] = target * width;
+                }
+            }
+        }
+
+        let start = ids.get(das.get_start_state()).map_or(DEAD, |&id| id * width);
+        Self {
+            columns,
+            byte_columns,
+            width,
+            table,
+            accepting,
+            start,
+        }
+    }
+
+    // Czy bajty wejścia tworzą słowo akceptowane; bajt odpowiada symbolowi
+    // według `Symbol::as_byte` (dla `char` – znaki ASCII)
+    pub fn matches(&self, input: &[u8]) -> bool {
+        let mut state = self.start;
+        for &byte in input {
+            state = self.table[(state + self.byte_columns[byte as usize]) as usize];
+            if state == DEAD {
+                return false;
+            }
+        }
+        self.accepting[(state / self.width) as usize]
+    }
+
+    // Wolniejsza ścieżka dla dowolnych symboli (mapowanie przez HashMap)
+    pub fn accepts(&self, input: impl IntoIterator<Item = S>) -> bool {
+        let mut state = self.start;
+        for symbol in input {
+            let column = self.columns.get(&symbol).copied().unwrap_or(0);
+            state = self.table[(state + column) as usize];
+            if state == DEAD {
+                return false;
+            }
+        }
+        self.accepting[(state / self.width) as usize]
+    }
+
+    // Liczba stanów bez stanu martwego
+    pub fn state_count(&self) -> usize {
+        self.accepting.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::automats::ENAS::ENAS;
+    use crate::automats::Shortlex::all_words;
+
+    #[test]
+    fn agrees_with_das() {
+        let das = ENAS::from_regex("(ab|b)*a?").unwrap().determinize();
+        let compiled = das.compile();
+        for word in all_words(&['a', 'b', 'c'], 6) {
+            let expected = das.accepts(word.chars());
+            assert_eq!(compiled.matches(word.as_bytes()), expected, "{:?}", word);
+            assert_eq!(compiled.accepts(word.chars()), expected, "{:?}", word);
+        }
+    }
+
+    #[test]
+    fn non_ascii_symbols_only_on_slow_path() {
+        let compiled = ENAS::from_regex("ż+").unwrap().determinize().compile();
+        assert!(compiled.accepts("żż".chars()));
+        assert!(!compiled.matches("żż".as_bytes()));
+    }
+
+    #[test]
+    fn missing_start_state_rejects_everything() {
+        let mut das = ENAS::from_regex("a*").unwrap().determinize();
+        das.set_start_state("brak");
+        let compiled = das.compile();
+        assert!(!compiled.matches(b""));
+        assert!(!compiled.matches(b"aa"));
+        assert_eq!(compiled.state_count(), das.get_states().len());
+    }
+}
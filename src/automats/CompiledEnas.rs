@@ -0,0 +1,300 @@
+use crate::elements::Label::Label;
+use crate::elements::Node::NodeBase;
+use crate::elements::Symbol::Symbol;
+use crate::automats::BitSet::BitSet;
+use crate::automats::ENAS::ENAS;
+use std::collections::{HashMap, VecDeque};
+
+// Domyślny limit stanów zapamiętanych przez `LazyDfa`; po jego przekroczeniu
+// pamięć podręczna jest czyszczona
+pub const DEFAULT_CACHE_STATES: usize = 10_000;
+// Najmniejszy limit, który da się dotrzymać: po wyczyszczeniu pamięci
+// podręcznej trafiają do niej stan martwy, startowy, bieżący i docelowy
+const MIN_CACHE_STATES: usize = 4;
+
+// Nieznane jeszcze przejście w tabeli `LazyDfa`
+const UNKNOWN: u32 = u32::MAX;
+// Pusty zbiór stanów – zawsze stan 0 w `LazyDfa`
+const DEAD: u32 = 0;
+
+// ENAS z ponumerowanymi stanami. Domknięcia ε są policzone z góry: dla każdej
+// pary (stan, kolumna) przechowywany jest już domknięty zbiór stanów docelowych,
+// więc krok symulacji to suma bitowa tych zbiorów dla stanów bieżących.
+#[derive(Clone)]
+pub struct CompiledEnas<S: Symbol = char> {
+    // symbol → kolumna (kolumna 0 to symbole spoza alfabetu)
+    columns: HashMap<S, u32>,
+    byte_columns: [u32; 256],
+    width: usize,
+    size: usize,
+    transitions: Vec<BitSet>,
+    start: BitSet,
+    accepting: BitSet,
+}
+
+impl<S: Symbol> ENAS<S> {
+    pub fn compile(&self) -> CompiledEnas<S> {
+        CompiledEnas::new(self)
+    }
+}
+
+impl<S: Symbol> CompiledEnas<S> {
+    pub fn new(enas: &ENAS<S>) -> Self {
+        let symbols = enas.alphabet().symbols();
+        let width = symbols.len() + 1;
+        let columns: HashMap<S, u32> = symbols.iter().cloned().zip(1..).collect();
+        let mut byte_columns = [0; 256];
+        for (symbol, &column) in &columns {
+            if let Some(byte) = symbol.as_byte() {
+                byte_columns[byte as usize] = column;
+            }
+        }
+
+        let mut names: Vec<&String> = enas.get_states().keys().collect();
+        names.sort();
+        let ids: HashMap<&str, usize> = names.iter().enumerate().map(|(i, n)| (n.as_str(), i)).collect();
+        let size = names.len();
+        // Przejścia do nieistniejących stanów są pomijane
+        let targets = |name: &str, label: &Label<S>| -> Vec<usize> {
+            enas.get_states()[name]
+                .get_connections()
+                .get(label)
+                .into_iter()
+                .flatten()
+                .filter_map(|t| ids.get(t.as_str()).copied())
+                .collect()
+        };
+
+        let closures: Vec<BitSet> = names
+            .iter()
+            .map(|name| {
+                let mut closure = BitSet::new(size);
+                let mut queue = VecDeque::from([ids[name.as_str()]]);
+                closure.insert(ids[name.as_str()]);
+                while let Some(state) = queue.pop_front() {
+                    for target in targets(names[state], &Label::Epsilon) {
+                        if closure.insert(target) {
+                            queue.push_back(target);
+                        }
+                    }
+                }
+                closure
+            })
+            .collect();
+
+        let mut transitions = vec![BitSet::new(size); size * width];
+        let mut accepting = BitSet::new(size);
+        for (id, name) in names.iter().enumerate() {
+            if enas.get_states()[name.as_str()].is_accepting() {
+                accepting.insert(id);
+            }
+            for (symbol, &column) in &columns {
+                for target in targets(name, &Label::Symbol(symbol.clone())) {
+                    transitions[id * width + column as usize].union_with(&closures[target]);
+                }
+            }
+        }
+
+        let start = match ids.get(enas.get_start_state()) {
+            Some(&id) => closures[id].clone(),
+            None => BitSet::new(size),
+        };
+        Self {
+            columns,
+            byte_columns,
+            width,
+            size,
+            transitions,
+            start,
+            accepting,
+        }
+    }
+
+    // Zbiór stanów po przeczytaniu symbolu z kolumny `column`
+    fn step(&self, current: &BitSet, column: usize, next: &mut BitSet) {
+        next.clear();
+        if column == 0 {
+            return;
+        }
+        for state in current.iter() {
+            next.union_with(&self.transitions[state * self.width + column]);
+        }
+    }
+
+    fn run(&self, columns: impl IntoIterator<Item = usize>) -> bool {
+        let mut current = self.start.clone();
+        let mut next = BitSet::new(self.size);
+        for column in columns {
+            self.step(&current, column, &mut next);
+            std::mem::swap(&mut current, &mut next);
+            if current.is_empty() {
+                return false;
+            }
+        }
+        current.intersects(&self.accepting)
+    }
+
+    // Bajt odpowiada symbolowi według `Symbol::as_byte` (dla `char` – znaki ASCII)
+    pub fn matches(&self, input: &[u8]) -> bool {
+        self.run(input.iter().map(|&b| self.byte_columns[b as usize] as usize))
+    }
+
+    pub fn accepts(&self, input: impl IntoIterator<Item = S>) -> bool {
+        self.run(input.into_iter().map(|s| self.columns.get(&s).copied().unwrap_or(0) as usize))
+    }
+
+    pub fn state_count(&self) -> usize {
+        self.size
+    }
+
+    pub fn lazy_dfa(&self) -> LazyDfa<'_, S> {
+        LazyDfa::new(self, DEFAULT_CACHE_STATES)
+    }
+}
+
+// DFA budowany w locie: zbiory stanów ENAS i przejścia między nimi są
+// zapamiętywane przy pierwszym użyciu, więc powtarzające się zbiory
+// (typowe przy długich wejściach) kosztują jedno odczytanie z tabeli
+pub struct LazyDfa<'a, S: Symbol = char> {
+    enas: &'a CompiledEnas<S>,
+    ids: HashMap<BitSet, u32>,
+    sets: Vec<BitSet>,
+    accepting: Vec<bool>,
+    // (stan DFA * szerokość + kolumna) → stan DFA albo UNKNOWN
+    table: Vec<u32>,
+    start: u32,
+    max_states: usize,
+}
+
+impl<'a, S: Symbol> LazyDfa<'a, S> {
+    pub fn new(enas: &'a CompiledEnas<S>, max_states: usize) -> Self {
+        let mut dfa = Self {
+            enas,
+            ids: HashMap::new(),
+            sets: Vec::new(),
+            accepting: Vec::new(),
+            table: Vec::new(),
+            start: DEAD,
+            max_states: max_states.max(MIN_CACHE_STATES),
+        };
+        dfa.reset();
+        dfa
+    }
+
+    // Czyści pamięć podręczną, zostawiając stan martwy i startowy
+    fn reset(&mut self) {
+        self.ids.clear();
+        self.sets.clear();
+        self.accepting.clear();
+        self.table.clear();
+        self.add(BitSet::new(self.enas.size));
+        self.table.fill(DEAD);
+        self.start = self.add(self.enas.start.clone());
+    }
+
+    fn add(&mut self, set: BitSet) -> u32 {
+        if let Some(&id) = self.ids.get(&set) {
+            return id;
+        }
+        let id = self.sets.len() as u32;
+        self.accepting.push(set.intersects(&self.enas.accepting));
+        self.table.extend(std::iter::repeat_n(UNKNOWN, self.enas.width));
+        self.ids.insert(set.clone(), id);
+        self.sets.push(set);
+        id
+    }
+
+    // Wyznacza i zapamiętuje nieznane przejście
+    fn compute(&mut self, mut state: u32, column: usize) -> u32 {
+        if self.sets.len() >= self.max_states {
+            let current = self.sets[state as usize].clone();
+            self.reset();
+            state = self.add(current);
+        }
+        let mut next = BitSet::new(self.enas.size);
+        self.enas.step(&self.sets[state as usize], column, &mut next);
+        let target = self.add(next);
+        self.table[state as usize * self.enas.width + column] = target;
+        target
+    }
+
+    fn run(&mut self, columns: impl IntoIterator<Item = usize>) -> bool {
+        let mut state = self.start;
+        for column in columns {
+            state = match self.table[state as usize * self.enas.width + column] {
+                UNKNOWN => self.compute(state, column),
+                next => next,
+            };
+            if state == DEAD {
+                return false;
+            }
+        }
+        self.accepting[state as usize]
+    }
+
+    pub fn matches(&mut self, input: &[u8]) -> bool {
+        let enas = self.enas;
+        self.run(input.iter().map(|&b| enas.byte_columns[b as usize] as usize))
+    }
+
+    pub fn accepts(&mut self, input: impl IntoIterator<Item = S>) -> bool {
+        let enas = self.enas;
+        self.run(input.into_iter().map(|s| enas.columns.get(&s).copied().unwrap_or(0) as usize))
+    }
+
+    // Liczba zapamiętanych stanów DFA (łącznie z martwym)
+    pub fn cached_states(&self) -> usize {
+        self.sets.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::automats::Shortlex::all_words;
+
+    #[test]
+    fn agrees_with_enas() {
+        // Konstrukcja Thompsona daje tu ponad 64 stany – zbiory zajmują kilka słów
+        let enas = ENAS::from_regex("(a|b)*a(a|b)(a|b)(a|b)(a|b)(a|b)(a|b)(a|b)|c?(ab)+").unwrap();
+        let compiled = enas.compile();
+        assert!(compiled.state_count() > 64);
+        let mut dfa = compiled.lazy_dfa();
+        for word in all_words(&['a', 'b', 'c'], 7) {
+            let expected = enas.accepts(word.chars());
+            assert_eq!(compiled.matches(word.as_bytes()), expected, "{:?}", word);
+            assert_eq!(compiled.accepts(word.chars()), expected, "{:?}", word);
+            assert_eq!(dfa.matches(word.as_bytes()), expected, "{:?}", word);
+        }
+    }
+
+    #[test]
+    fn lazy_dfa_respects_cache_limit() {
+        let enas = ENAS::from_regex("(a|b)*a(a|b)(a|b)(a|b)").unwrap();
+        let compiled = enas.compile();
+        let mut dfa = LazyDfa::new(&compiled, 4);
+        for word in all_words(&['a', 'b'], 8) {
+            assert_eq!(dfa.accepts(word.chars()), enas.accepts(word.chars()), "{:?}", word);
+            assert!(dfa.cached_states() <= 4);
+        }
+    }
+
+    #[test]
+    fn lazy_dfa_raises_too_small_limit() {
+        let enas = ENAS::from_regex("(a|b)*a(a|b)(a|b)").unwrap();
+        let compiled = enas.compile();
+        let mut dfa = LazyDfa::new(&compiled, 2);
+        for word in all_words(&['a', 'b'], 7) {
+            assert_eq!(dfa.accepts(word.chars()), enas.accepts(word.chars()), "{:?}", word);
+            assert!(dfa.cached_states() <= MIN_CACHE_STATES);
+        }
+    }
+
+    #[test]
+    fn symbols_outside_alphabet_reject() {
+        let compiled = ENAS::from_regex("a*").unwrap().compile();
+        assert!(compiled.matches(b"aaa"));
+        assert!(!compiled.matches(b"aza"));
+        assert!(!compiled.lazy_dfa().accepts("aza".chars()));
+    }
+}
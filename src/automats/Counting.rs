@@ -0,0 +1,246 @@
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, Signed, Zero};
+use std::fmt;
+
+// Liniowa rekurencja a(n) = c1·a(n-1) + ... + ck·a(n-k), spełniona dla
+// n >= initial.len(); `initial` to wyrazy a(0), a(1), ...
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recurrence {
+    pub coefficients: Vec<BigInt>,
+    pub initial: Vec<BigInt>,
+}
+
+// Funkcja tworząca A(x) = P(x) / Q(x); współczynniki od najniższej potęgi
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeneratingFunction {
+    pub numerator: Vec<BigInt>,
+    pub denominator: Vec<BigInt>,
+}
+
+impl Recurrence {
+    pub fn term(&self, n: usize) -> BigInt {
+        let mut terms = self.initial.clone();
+        while terms.len() <= n {
+            let k = terms.len();
+            let next = self
+                .coefficients
+                .iter()
+                .enumerate()
+                .map(|(i, c)| c * &terms[k - 1 - i])
+                .sum();
+            terms.push(next);
+        }
+        terms[n].clone()
+    }
+
+    pub fn generating_function(&self) -> GeneratingFunction {
+        // Q(x) = 1 - c1·x - ... - ck·x^k
+        let mut denominator = vec![BigInt::one()];
+        denominator.extend(self.coefficients.iter().map(|c| -c));
+        // P(x) = Q(x)·A(x) obcięte do stopnia < liczby wyrazów początkowych
+        let numerator = (0..self.initial.len())
+            .map(|n| {
+                denominator
+                    .iter()
+                    .take(n + 1)
+                    .enumerate()
+                    .map(|(i, q)| q * &self.initial[n - i])
+                    .sum()
+            })
+            .collect();
+        GeneratingFunction {
+            numerator: trim_zeros(numerator),
+            denominator,
+        }
+    }
+}
+
+fn trim_zeros(mut polynomial: Vec<BigInt>) -> Vec<BigInt> {
+    while polynomial.last().is_some_and(|c| c.is_zero()) {
+        polynomial.pop();
+    }
+    polynomial
+}
+
+// Wielomian charakterystyczny macierzy całkowitoliczbowej (metoda
+// Faddiejewa–LeVerriera): zwraca q1..qm, gdzie p(λ) = λ^m + q1·λ^(m-1) + ... + qm.
+// Dzielenie przez k jest zawsze dokładne.
+pub(crate) fn characteristic_polynomial(matrix: &[Vec<BigInt>]) -> Vec<BigInt> {
+    let m = matrix.len();
+    let multiply = |a: &[Vec<BigInt>], b: &[Vec<BigInt>]| -> Vec<Vec<BigInt>> {
+        (0..m)
+            .map(|i| {
+                (0..m)
+                    .map(|j| (0..m).map(|k| &a[i][k] * &b[k][j]).sum())
+                    .collect()
+            })
+            .collect()
+    };
+    let trace = |a: &[Vec<BigInt>]| -> BigInt { (0..m).map(|i| a[i][i].clone()).sum() };
+
+    let mut coefficients = Vec::with_capacity(m);
+    let mut n: Vec<Vec<BigInt>> = (0..m)
+        .map(|i| (0..m).map(|j| if i == j { BigInt::one() } else { BigInt::zero() }).collect())
+        .collect();
+    for k in 1..=m {
+        if k > 1 {
+            n = multiply(matrix, &n);
+            let previous: &BigInt = &coefficients[k - 2];
+            for (i, row) in n.iter_mut().enumerate() {
+                row[i] += previous;
+            }
+        }
+        let an = multiply(matrix, &n);
+        coefficients.push(-trace(&an) / BigInt::from(k));
+    }
+    coefficients
+}
+
+// Liczby słów długości 0..=n, obliczane programowaniem dynamicznym na
+// macierzy przejść (`matrix[i][j]` – liczba symboli prowadzących z i do j)
+pub(crate) fn count_paths(matrix: &[Vec<BigUint>], start: usize, accepting: &[bool], n: usize) -> Vec<BigUint> {
+    let m = matrix.len();
+    let mut counts = Vec::with_capacity(n + 1);
+    let mut current = vec![BigUint::zero(); m];
+    if start < m {
+        current[start] = BigUint::one();
+    }
+    for length in 0..=n {
+        counts.push(
+            current
+                .iter()
+                .zip(accepting)
+                .filter(|(_, a)| **a)
+                .map(|(c, _)| c)
+                .sum(),
+        );
+        if length < n {
+            let mut next = vec![BigUint::zero(); m];
+            for (i, count) in current.iter().enumerate().filter(|(_, c)| !c.is_zero()) {
+                for (j, weight) in matrix[i].iter().enumerate().filter(|(_, w)| !w.is_zero()) {
+                    next[j] += count * weight;
+                }
+            }
+            current = next;
+        }
+    }
+    counts
+}
+
+fn format_term(f: &mut fmt::Formatter<'_>, first: bool, coefficient: &BigInt, term: &str) -> fmt::Result {
+    let sign = if coefficient.is_negative() { "-" } else { "+" };
+    let abs = coefficient.abs();
+    if first {
+        if coefficient.is_negative() {
+            write!(f, "-")?;
+        }
+    } else {
+        write!(f, " {} ", sign)?;
+    }
+    match (abs.is_one(), term.is_empty()) {
+        (true, false) => write!(f, "{}", term),
+        (_, true) => write!(f, "{}", abs),
+        (false, false) => write!(f, "{}·{}", abs, term),
+    }
+}
+
+fn format_polynomial(f: &mut fmt::Formatter<'_>, polynomial: &[BigInt]) -> fmt::Result {
+    let mut first = true;
+    for (power, c) in polynomial.iter().enumerate().filter(|(_, c)| !c.is_zero()) {
+        let term = match power {
+            0 => String::new(),
+            1 => "x".to_string(),
+            p => format!("x^{}", p),
+        };
+        format_term(f, first, c, &term)?;
+        first = false;
+    }
+    if first {
+        write!(f, "0")?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.initial.is_empty() {
+            return write!(f, "a(n) = 0");
+        }
+        write!(f, "a(n) = ")?;
+        let mut first = true;
+        for (i, c) in self.coefficients.iter().enumerate().filter(|(_, c)| !c.is_zero()) {
+            format_term(f, first, c, &format!("a(n-{})", i + 1))?;
+            first = false;
+        }
+        if first {
+            write!(f, "0")?;
+        }
+        write!(f, " dla n ≥ {}", self.initial.len())?;
+        let initial: Vec<String> = self.initial.iter().map(|a| a.to_string()).collect();
+        write!(f, "; wyrazy początkowe: [{}]", initial.join(", "))
+    }
+}
+
+impl fmt::Display for GeneratingFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        format_polynomial(f, &self.numerator)?;
+        write!(f, ") / (")?;
+        format_polynomial(f, &self.denominator)?;
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::automats::ENAS::ENAS;
+
+    fn counts(values: &[u32]) -> Vec<BigUint> {
+        values.iter().map(|&v| BigUint::from(v)).collect()
+    }
+
+    // Pierwsze wyrazy rozwinięcia P(x) / Q(x) w szereg potęgowy (Q(0) = 1)
+    fn series(function: &GeneratingFunction, n: usize) -> Vec<BigInt> {
+        let mut terms: Vec<BigInt> = Vec::new();
+        for k in 0..n {
+            let mut term = function.numerator.get(k).cloned().unwrap_or_default();
+            for (i, q) in function.denominator.iter().enumerate().skip(1).take(k) {
+                term -= q * &terms[k - i];
+            }
+            terms.push(term);
+        }
+        terms
+    }
+
+    #[test]
+    fn words_without_double_b() {
+        // Liczby Fibonacciego: słowa nad {a, b} bez "bb"
+        let enas = ENAS::from_regex("(a|ba)*(b|)").unwrap();
+        assert_eq!(enas.count_words_up_to(6), counts(&[1, 2, 3, 5, 8, 13, 21]));
+        let recurrence = enas.recurrence();
+        let function = enas.generating_function();
+        let expected: Vec<BigInt> = (0..30).map(|n| BigInt::from(enas.count_words(n))).collect();
+        assert_eq!((0..30).map(|n| recurrence.term(n)).collect::<Vec<_>>(), expected);
+        assert_eq!(series(&function, 30), expected);
+    }
+
+    #[test]
+    fn ambiguous_paths_are_counted_once() {
+        let enas = ENAS::from_regex("(a|a)*|a*").unwrap();
+        assert_eq!(enas.count_words_up_to(3), counts(&[1, 1, 1, 1]));
+    }
+
+    #[test]
+    fn finite_language() {
+        let enas = ENAS::from_regex("ab|c|ba").unwrap();
+        assert_eq!(enas.count_words_up_to(4), counts(&[0, 1, 2, 0, 0]));
+        assert_eq!(enas.recurrence().term(10), BigInt::zero());
+    }
+
+    #[test]
+    fn counts_exceed_machine_words() {
+        let enas = ENAS::from_regex("[a-z]*").unwrap();
+        assert_eq!(enas.count_words(20), BigUint::from(26u32).pow(20));
+    }
+}
@@ -1,16 +1,47 @@
 use crate::elements::Alphabet::Alphabet;
-use crate::elements::Node::{NodeBase, DASNode};
-use std::collections::{HashMap, HashSet};
+use crate::elements::Label::Label;
+use crate::elements::Node::{NodeBase, DASNode, ENASNode};
+use crate::elements::Symbol::Symbol;
+use crate::automats::ENAS::ENAS;
+use crate::automats::ValidationError::ValidationError;
+use crate::automats::Verdict::{Verdict, Witness};
+use crate::automats::Graph::LabeledGraph;
+use crate::automats::Shortlex::LanguageIter;
+use crate::automats::Counting::{characteristic_polynomial, count_paths, GeneratingFunction, Recurrence};
+use num_bigint::{BigInt, BigUint};
+use num_traits::Zero;
+use std::collections::{HashMap, HashSet, VecDeque};
 
+// Nazwa stanu-pułapki dodawanego przez `DAS::complete`
+pub const SINK_STATE: &str = "sink";
 
-pub struct DAS {
-    pub(crate) alphabet: Alphabet,
-    states: HashMap<String, DASNode>,
+// Pierwsza z nazw "sink", "sink1", "sink2", … którą `usable` dopuszcza – np.
+// nazwa, której nie nosi jeszcze żaden stan
+pub fn sink_name(usable: impl Fn(&str) -> bool) -> String {
+    let mut sink = SINK_STATE.to_string();
+    let mut suffix = 1;
+    while !usable(&sink) {
+        sink = format!("{}{}", SINK_STATE, suffix);
+        suffix += 1;
+    }
+    sink
+}
+
+#[derive(Clone)]
+pub struct DAS<S: Symbol = char> {
+    pub(crate) alphabet: Alphabet<S>,
+    states: HashMap<String, DASNode<S>>,
     start_state: String,
 }
 
-impl DAS {
-    pub fn new(alphabet: Alphabet) -> Self {
+impl DAS<char> {
+    pub fn process(&self, input: &str) -> bool {
+        self.accepts(input.chars())
+    }
+}
+
+impl<S: Symbol> DAS<S> {
+    pub fn new(alphabet: Alphabet<S>) -> Self {
         Self {
             alphabet,
             states: HashMap::new(),
@@ -18,7 +49,7 @@ impl DAS {
         }
     }
 
-    pub fn add_state(&mut self, node: DASNode) {
+    pub fn add_state(&mut self, node: DASNode<S>) {
         let name = node.get_name().to_string();
         self.states.insert(name, node);
     }
@@ -27,49 +58,547 @@ impl DAS {
         self.start_state = name.to_string();
     }
 
-    pub fn process(&self, input: &str) -> bool {
-        let mut current = self.start_state.clone();
-        for c in input.chars() {
-            let node = match self.states.get(&current) {
+    pub fn alphabet(&self) -> &Alphabet<S> {
+        &self.alphabet
+    }
+
+    pub fn get_states(&self) -> &HashMap<String, DASNode<S>> {
+        &self.states
+    }
+
+    pub fn get_start_state(&self) -> &str {
+        &self.start_state
+    }
+
+    pub fn accepts(&self, input: impl IntoIterator<Item = S>) -> bool {
+        let mut current = self.start_state.as_str();
+        for c in input {
+            let node = match self.states.get(current) {
                 Some(n) => n,
                 None => return false,
             };
             if let Some(next) = node.get_connections().get(&c) {
-                current = next.clone();
+                current = next;
             } else {
                 return false;
             }
         }
-        self.states.get(&current).map_or(false, |n| n.is_accepting())
+        self.states.get(current).is_some_and(|n| n.is_accepting())
+    }
+
+    // Uzupełnia częściowy DAS: każde brakujące przejście prowadzi do nowego,
+    // nieakceptującego stanu-pułapki. Zwraca nazwę dodanego stanu albo None,
+    // jeśli automat był już pełny.
+    pub fn complete(&mut self) -> Option<String> {
+        let symbols = self.alphabet.symbols();
+        let is_partial = self
+            .states
+            .values()
+            .any(|n| symbols.iter().any(|c| !n.get_connections().contains_key(c)));
+        if !is_partial {
+            return None;
+        }
+
+        let sink = sink_name(|name| !self.states.contains_key(name));
+
+        for node in self.states.values_mut() {
+            for symbol in &symbols {
+                if !node.get_connections().contains_key(symbol) {
+                    node.add_connection(symbol.clone(), &sink);
+                }
+            }
+        }
+        let mut trap = DASNode::new(&sink, false);
+        for symbol in &symbols {
+            trap.add_connection(symbol.clone(), &sink);
+        }
+        self.add_state(trap);
+        Some(sink)
+    }
+
+    pub fn validate(&self) -> Vec<ValidationError<S>> {
+        self.validate_with(false)
     }
 
-    pub fn validate(&self) -> Vec<String> {
+    // allow_partial: brak przejścia nie jest błędem – `process` i tak
+    // odrzuca wtedy słowo (niejawne odrzucenie)
+    pub fn validate_with(&self, allow_partial: bool) -> Vec<ValidationError<S>> {
         let mut errors = Vec::new();
         let state_names: HashSet<_> = self.states.keys().cloned().collect();
-        let alphabet_vec: Vec<char> = self.alphabet.alphabet.iter().cloned().collect();
+        let alphabet_vec = self.alphabet.symbols();
+
+        for symbol in alphabet_vec.iter().filter(|s| s.is_reserved()) {
+            errors.push(ValidationError::ReservedSymbol { symbol: symbol.clone() });
+        }
 
         for (state, node) in &self.states {
-            for (_symbol, target) in node.get_connections() {
+            for (symbol, target) in node.get_connections() {
                 if !state_names.contains(target) {
-                    errors.push(format!(
-                        "Stan '{}' ma połączenie do nieistniejącego stanu '{}'.",
-                        state, target
-                    ));
+                    errors.push(ValidationError::UnknownTarget {
+                        state: state.clone(),
+                        symbol: symbol.clone(),
+                        target: target.clone(),
+                    });
                 }
             }
         }
 
+        if allow_partial {
+            errors.sort();
+            return errors;
+        }
+
         for (state, node) in &self.states {
-            for &symbol in &alphabet_vec {
-                if !node.get_connections().contains_key(&symbol) {
-                    errors.push(format!(
-                        "Stan '{}' nie ma połączenia dla znaku '{}'.",
-                        state, symbol
-                    ));
+            for symbol in &alphabet_vec {
+                if !node.get_connections().contains_key(symbol) {
+                    errors.push(ValidationError::MissingTransition {
+                        state: state.clone(),
+                        symbol: symbol.clone(),
+                    });
                 }
             }
         }
 
+        // Stała kolejność komunikatów niezależnie od kolejności w HashMap
+        errors.sort();
         errors
     }
+
+    // Stany osiągalne ze stanu startowego
+    pub fn reachable_states(&self) -> HashSet<String> {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        if self.states.contains_key(&self.start_state) {
+            visited.insert(self.start_state.clone());
+            queue.push_back(self.start_state.as_str());
+        }
+        while let Some(state) = queue.pop_front() {
+            for target in self.states[state].get_connections().values() {
+                if self.states.contains_key(target) && visited.insert(target.clone()) {
+                    queue.push_back(target);
+                }
+            }
+        }
+        visited
+    }
+
+    // Stany, z których da się dojść do stanu akceptującego
+    pub fn coreachable_states(&self) -> HashSet<String> {
+        let mut reverse: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (name, node) in &self.states {
+            for target in node.get_connections().values() {
+                reverse.entry(target.as_str()).or_default().push(name.as_str());
+            }
+        }
+
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        for (name, node) in &self.states {
+            if node.is_accepting() {
+                visited.insert(name.clone());
+                queue.push_back(name.as_str());
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            for &source in reverse.get(state).into_iter().flatten() {
+                if visited.insert(source.to_string()) {
+                    queue.push_back(source);
+                }
+            }
+        }
+        visited
+    }
+
+    // Automat bez stanów nieosiągalnych i martwych. Przejścia do usuniętych
+    // stanów znikają, więc wynik może być niepełnym DAS.
+    pub fn trim(&self) -> DAS<S> {
+        let reachable = self.reachable_states();
+        let coreachable = self.coreachable_states();
+        let useful = |name: &String| reachable.contains(name) && coreachable.contains(name);
+
+        let mut trimmed = DAS::new(self.alphabet.clone());
+        trimmed.set_start_state(&self.start_state);
+        for (name, node) in self.states.iter().filter(|(name, _)| useful(name)) {
+            let mut trimmed_node = DASNode::new(name, node.is_accepting());
+            trimmed_node.set_position(node.get_position());
+            for (symbol, target) in node.get_connections() {
+                if useful(target) {
+                    trimmed_node.add_connection(symbol.clone(), target);
+                }
+            }
+            trimmed.add_state(trimmed_node);
+        }
+        trimmed
+    }
+
+    // Ten sam automat zapisany jako ε-NAS (bez przejść ε)
+    pub fn to_enas(&self) -> ENAS<S> {
+        let mut enas = ENAS::new(self.alphabet.clone());
+        enas.set_start_state(&self.start_state);
+        for (name, node) in &self.states {
+            let mut enas_node = ENASNode::new(name, node.is_accepting());
+            enas_node.set_position(node.get_position());
+            for (symbol, target) in node.get_connections() {
+                enas_node.add_connection(Label::Symbol(symbol.clone()), vec![target.clone()]);
+            }
+            enas.add_state(enas_node);
+        }
+        enas
+    }
+
+    // Ten sam automat ze stanami q0, q1, … ponumerowanymi w kolejności BFS od
+    // stanu startowego (np. po determinizacji, gdzie nazwy to zbiory stanów).
+    // Stany nieosiągalne dostają kolejne numery w porządku alfabetycznym.
+    pub fn renumbered(&self) -> DAS<S> {
+        let symbols = self.alphabet.symbols();
+        let mut order = vec![self.start_state.clone()];
+        let mut seen: HashSet<String> = order.iter().cloned().collect();
+        let mut i = 0;
+        while i < order.len() {
+            if let Some(node) = self.states.get(&order[i]) {
+                for symbol in &symbols {
+                    if let Some(target) = node.get_connections().get(symbol)
+                        && self.states.contains_key(target)
+                        && seen.insert(target.clone())
+                    {
+                        order.push(target.clone());
+                    }
+                }
+            }
+            i += 1;
+        }
+        let mut rest: Vec<&String> = self.states.keys().filter(|name| !seen.contains(*name)).collect();
+        rest.sort();
+        order.extend(rest.into_iter().cloned());
+
+        let names: HashMap<&String, String> = order.iter().enumerate().map(|(i, name)| (name, format!("q{}", i))).collect();
+        let mut renumbered = DAS::new(self.alphabet.clone());
+        renumbered.set_start_state(&names[&self.start_state]);
+        for (name, node) in &self.states {
+            let mut renumbered_node = DASNode::new(&names[name], node.is_accepting());
+            renumbered_node.set_position(node.get_position());
+            for (symbol, target) in node.get_connections() {
+                // Przejście do nieistniejącego stanu zachowuje oryginalną nazwę
+                let target = names.get(target).cloned().unwrap_or_else(|| target.clone());
+                renumbered_node.add_connection(symbol.clone(), &target);
+            }
+            renumbered.add_state(renumbered_node);
+        }
+        renumbered
+    }
+
+    fn graph(&self) -> LabeledGraph<'_, S> {
+        let mut graph = LabeledGraph::new();
+        for (name, node) in &self.states {
+            for (symbol, target) in node.get_connections() {
+                graph.add_edge(name, Some(symbol.clone()), target);
+            }
+        }
+        graph
+    }
+
+    pub(crate) fn is_accepting_state(&self, name: &str) -> bool {
+        self.states.get(name).is_some_and(|n| n.is_accepting())
+    }
+
+    // Czy język jest pusty; świadek: najkrótsze akceptowane słowo
+    pub fn is_empty(&self) -> Verdict<S> {
+        match self.graph().shortest_word(&self.start_state, |s| self.is_accepting_state(s)) {
+            Some(word) => Verdict::new(false, Some(Witness::Accepted(word))),
+            None => Verdict::new(true, None),
+        }
+    }
+
+    // Czy automat akceptuje każde słowo nad alfabetem; świadek: najkrótsze
+    // odrzucane słowo. Brak przejścia traktowany jest jak odrzucenie.
+    pub fn is_universal(&self) -> Verdict<S> {
+        let symbols = self.alphabet.symbols();
+        let rejected = |word: Vec<S>| Verdict::new(false, Some(Witness::Rejected(word)));
+
+        if !self.is_accepting_state(&self.start_state) {
+            return rejected(Vec::new());
+        }
+        let mut words: HashMap<&str, Vec<S>> = HashMap::new();
+        let mut queue = VecDeque::new();
+        words.insert(&self.start_state, Vec::new());
+        queue.push_back(self.start_state.as_str());
+        // Stany sprawdzane są przy odkryciu, więc pierwsze znalezione
+        // odrzucane słowo jest najkrótsze
+        while let Some(state) = queue.pop_front() {
+            let word = words[state].clone();
+            for symbol in &symbols {
+                let mut next = word.clone();
+                next.push(symbol.clone());
+                match self.states[state].get_connections().get(symbol) {
+                    Some(target) if words.contains_key(target.as_str()) => {}
+                    Some(target) if self.is_accepting_state(target) => {
+                        words.insert(target, next);
+                        queue.push_back(target);
+                    }
+                    _ => return rejected(next),
+                }
+            }
+        }
+        Verdict::new(true, None)
+    }
+
+    // Czy język jest skończony – szuka cyklu w przyciętym automacie;
+    // świadek: pompowalny cykl
+    pub fn is_finite(&self) -> Verdict<S> {
+        let trimmed = self.trim();
+        let accepting: HashSet<String> = trimmed
+            .states
+            .values()
+            .filter(|n| n.is_accepting())
+            .map(|n| n.get_name().to_string())
+            .collect();
+        match trimmed.graph().pumpable_cycle(&trimmed.start_state, &accepting) {
+            Some(cycle) => Verdict::new(false, Some(cycle)),
+            None => Verdict::new(true, None),
+        }
+    }
+
+    pub fn accepts_epsilon(&self) -> Verdict<S> {
+        if self.accepts([]) {
+            Verdict::new(true, Some(Witness::Accepted(Vec::new())))
+        } else {
+            Verdict::new(false, Some(Witness::Rejected(Vec::new())))
+        }
+    }
+
+    // Słowa akceptowane w porządku shortlex, o długości co najwyżej `max_len`.
+    // Gałęzie prowadzące do stanów martwych są odcinane.
+    pub fn accepted_words(&self, max_len: usize) -> LanguageIter<'_, &str, S> {
+        let coreachable = self.coreachable_states();
+        let start = Some(self.start_state.as_str()).filter(|s| coreachable.contains(*s));
+        LanguageIter::new(
+            start,
+            self.alphabet.symbols(),
+            max_len,
+            move |state, c| {
+                self.states[*state]
+                    .get_connections()
+                    .get(c)
+                    .map(String::as_str)
+                    .filter(|t| coreachable.contains(*t))
+            },
+            |state| self.is_accepting_state(state),
+        )
+    }
+
+    // Słowa odrzucane w porządku shortlex. Stan None oznacza, że zabrakło
+    // przejścia – każde przedłużenie takiego słowa też jest odrzucane.
+    pub fn rejected_words(&self, max_len: usize) -> LanguageIter<'_, Option<&str>, S> {
+        LanguageIter::new(
+            Some(Some(self.start_state.as_str())),
+            self.alphabet.symbols(),
+            max_len,
+            |state, c| {
+                Some(
+                    state
+                        .and_then(|s| self.states.get(s))
+                        .and_then(|n| n.get_connections().get(c))
+                        .map(String::as_str),
+                )
+            },
+            |state| !state.is_some_and(|s| self.is_accepting_state(s)),
+        )
+    }
+
+    // Macierz przejść przyciętego automatu: matrix[i][j] to liczba symboli
+    // prowadzących ze stanu i do stanu j. Zwraca też indeks stanu startowego
+    // (równy rozmiarowi macierzy, gdy go brak) i stany akceptujące.
+    fn counting_matrix(&self) -> (Vec<Vec<BigUint>>, usize, Vec<bool>) {
+        let trimmed = self.trim();
+        let mut names: Vec<&String> = trimmed.states.keys().collect();
+        names.sort();
+        let index: HashMap<&str, usize> = names.iter().enumerate().map(|(i, n)| (n.as_str(), i)).collect();
+
+        let mut matrix = vec![vec![BigUint::zero(); names.len()]; names.len()];
+        for name in &names {
+            for target in trimmed.states[*name].get_connections().values() {
+                matrix[index[name.as_str()]][index[target.as_str()]] += 1u32;
+            }
+        }
+        let start = index.get(trimmed.start_state.as_str()).copied().unwrap_or(names.len());
+        let accepting = names.iter().map(|n| trimmed.states[*n].is_accepting()).collect();
+        (matrix, start, accepting)
+    }
+
+    // Liczba akceptowanych słów długości dokładnie n
+    pub fn count_words(&self, n: usize) -> BigUint {
+        self.count_words_up_to(n).pop().unwrap_or_default()
+    }
+
+    // Liczby akceptowanych słów długości 0, 1, ..., n
+    pub fn count_words_up_to(&self, n: usize) -> Vec<BigUint> {
+        let (matrix, start, accepting) = self.counting_matrix();
+        count_paths(&matrix, start, &accepting, n)
+    }
+
+    // Rekurencja liniowa spełniana przez liczby słów (z twierdzenia
+    // Cayleya–Hamiltona dla macierzy przejść przyciętego automatu)
+    pub fn recurrence(&self) -> Recurrence {
+        let (matrix, start, accepting) = self.counting_matrix();
+        let integer: Vec<Vec<BigInt>> = matrix
+            .iter()
+            .map(|row| row.iter().map(|w| BigInt::from(w.clone())).collect())
+            .collect();
+        let mut coefficients: Vec<BigInt> = characteristic_polynomial(&integer).into_iter().map(|q| -q).collect();
+        while coefficients.last().is_some_and(|c| c.is_zero()) {
+            coefficients.pop();
+        }
+        let initial = match matrix.len() {
+            0 => Vec::new(),
+            m => count_paths(&matrix, start, &accepting, m - 1).into_iter().map(BigInt::from).collect(),
+        };
+        Recurrence { coefficients, initial }
+    }
+
+    pub fn generating_function(&self) -> GeneratingFunction {
+        self.recurrence().generating_function()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::{Automat, Dsl};
+
+    fn das(text: &str) -> DAS {
+        match Dsl::parse(text).unwrap() {
+            Automat::DAS(das) => das,
+            Automat::ENAS(_) => panic!("automat niedeterministyczny: {}", text),
+        }
+    }
+
+    fn language(das: &DAS) -> Vec<String> {
+        das.accepted_words(6).map(|w| w.into_iter().collect()).collect()
+    }
+
+    #[test]
+    fn complete_adds_sink_and_keeps_language() {
+        let mut automat = das("alphabet a b\nstart q0\naccept q1\nq0 -a-> q1\nq1 -a,b-> q1");
+        let before = language(&automat);
+        assert_eq!(automat.complete().as_deref(), Some(SINK_STATE));
+        assert!(automat.validate().is_empty());
+        assert!(!automat.get_states()[SINK_STATE].is_accepting());
+        assert_eq!(language(&automat), before);
+        assert_eq!(automat.complete(), None);
+    }
+
+    #[test]
+    fn complete_does_not_reuse_user_sink() {
+        // Akceptujący stan "sink" użytkownika – skierowanie do niego brakujących
+        // przejść zmieniłoby język
+        let mut automat = das("alphabet a b\nstart q0\naccept sink\nq0 -a-> sink\nsink -a,b-> sink");
+        let before = language(&automat);
+        assert_eq!(automat.complete().as_deref(), Some("sink1"));
+        assert_eq!(automat.get_states()["q0"].get_connections()[&'b'], "sink1");
+        assert!(automat.get_states()["sink"].is_accepting());
+        assert_eq!(language(&automat), before);
+    }
+
+    #[test]
+    fn validate_with_partial() {
+        let automat = das("alphabet a b\nstart q0\naccept q0\nq0 -a-> q0");
+        assert!(automat.validate_with(true).is_empty());
+        assert_eq!(
+            automat.validate_with(false),
+            [ValidationError::MissingTransition { state: "q0".to_string(), symbol: 'b' }]
+        );
+        assert_eq!(automat.validate(), automat.validate_with(false));
+    }
+
+    #[test]
+    fn validate_reports_unknown_target_even_if_partial() {
+        let mut automat = das("alphabet a\nstart q0\nq0 -a-> q0");
+        let mut node = DASNode::new("q0", false);
+        node.add_connection('a', "brak");
+        automat.add_state(node);
+        assert_eq!(
+            automat.validate_with(true),
+            [ValidationError::UnknownTarget { state: "q0".to_string(), symbol: 'a', target: "brak".to_string() }]
+        );
+    }
+
+    fn names(states: impl IntoIterator<Item = String>) -> Vec<String> {
+        let mut names: Vec<String> = states.into_iter().collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn trim_removes_unreachable_and_dead_states() {
+        // q3 jest nieosiągalny, q2 martwy
+        let automat = das("alphabet a b\nstart q0\naccept q1\nq0 -a-> q1\nq0 -b-> q2\nq2 -a,b-> q2\nq3 -a-> q1");
+        assert_eq!(names(automat.reachable_states()), ["q0", "q1", "q2"]);
+        assert_eq!(names(automat.coreachable_states()), ["q0", "q1", "q3"]);
+        let trimmed = automat.trim();
+        assert_eq!(names(trimmed.get_states().keys().cloned()), ["q0", "q1"]);
+        assert_eq!(trimmed.get_start_state(), "q0");
+        assert!(trimmed.get_states()["q0"].get_connections().get(&'b').is_none());
+        assert_eq!(language(&trimmed), language(&automat));
+    }
+
+    #[test]
+    fn trim_of_empty_language() {
+        let automat = das("alphabet a\nstart q0\naccept q1\nq0 -a-> q0");
+        let trimmed = automat.trim();
+        // Stan startowy też jest martwy – zostaje tylko jego nazwa
+        assert!(trimmed.get_states().is_empty());
+        assert_eq!(trimmed.get_start_state(), "q0");
+        assert!(language(&trimmed).is_empty());
+        assert!(!trimmed.accepts("".chars()));
+    }
+
+    // Sprawdza, czy świadek naprawdę potwierdza werdykt: akceptowane słowo jest
+    // akceptowane, odrzucane odrzucane, a pompowanie cyklu nie wychodzi z języka
+    fn check_witness(das: &DAS, witness: &Witness) {
+        match witness {
+            Witness::Accepted(word) => assert!(das.accepts(word.iter().copied())),
+            Witness::Rejected(word) => assert!(!das.accepts(word.iter().copied())),
+            Witness::Cycle { prefix, cycle, suffix } => {
+                assert!(!cycle.is_empty());
+                for n in 0..4 {
+                    let word = prefix.iter().chain(cycle.iter().cycle().take(n * cycle.len())).chain(suffix);
+                    assert!(das.accepts(word.copied()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn witnesses_are_valid() {
+        // Słowa zawierające "ab"
+        let automat = das("alphabet a b\nstart q0\naccept q2\nq0 -a-> q1\nq0 -b-> q0\n\
+                           q1 -a-> q1\nq1 -b-> q2\nq2 -a,b-> q2");
+        let verdicts = [automat.is_empty(), automat.is_universal(), automat.is_finite(), automat.accepts_epsilon()];
+        for verdict in verdicts {
+            assert!(!verdict.holds);
+            check_witness(&automat, &verdict.witness.unwrap());
+        }
+        assert_eq!(automat.is_empty().witness, Some(Witness::Accepted(vec!['a', 'b'])));
+        assert_eq!(automat.is_universal().witness, Some(Witness::Rejected(vec![])));
+    }
+
+    #[test]
+    fn witnesses_of_holding_properties() {
+        let universal = das("alphabet a b\nstart q0\naccept q0\nq0 -a,b-> q0");
+        assert_eq!(universal.is_universal(), Verdict::new(true, None));
+        let verdict = universal.accepts_epsilon();
+        assert!(verdict.holds);
+        check_witness(&universal, &verdict.witness.unwrap());
+
+        // Niepełny automat: brak przejścia odrzuca słowo
+        let partial = das("alphabet a b\nstart q0\naccept q0 q1\nq0 -a-> q1");
+        let verdict = partial.is_universal();
+        assert!(!verdict.holds);
+        check_witness(&partial, &verdict.witness.unwrap());
+        assert_eq!(partial.is_finite(), Verdict::new(true, None));
+
+        let empty = das("alphabet a\nstart q0\naccept q1\nq0 -a-> q0");
+        assert_eq!(empty.is_empty(), Verdict::new(true, None));
+        assert_eq!(empty.is_finite(), Verdict::new(true, None));
+    }
 }
@@ -1,15 +1,31 @@
 use crate::elements::Alphabet::Alphabet;
-use crate::elements::Node::{NodeBase, ENASNode};
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::elements::Node::{NodeBase, DASNode, ENASNode};
+use crate::elements::Label::Label;
+use crate::elements::Symbol::Symbol;
+use crate::automats::DAS::DAS;
+use crate::automats::ValidationError::ValidationError;
+use crate::automats::Verdict::{Verdict, Witness};
+use crate::automats::Graph::LabeledGraph;
+use crate::automats::Shortlex::LanguageIter;
+use crate::automats::Counting::{GeneratingFunction, Recurrence};
+use num_bigint::BigUint;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 
-pub struct ENAS {
-    pub(crate) alphabet: Alphabet,
-    states: HashMap<String, ENASNode>,
+#[derive(Clone)]
+pub struct ENAS<S: Symbol = char> {
+    pub(crate) alphabet: Alphabet<S>,
+    states: HashMap<String, ENASNode<S>>,
     start_state: String,
 }
 
-impl ENAS {
-    pub fn new(alphabet: Alphabet) -> Self {
+impl ENAS<char> {
+    pub fn process(&self, input: &str) -> bool {
+        self.accepts(input.chars())
+    }
+}
+
+impl<S: Symbol> ENAS<S> {
+    pub fn new(alphabet: Alphabet<S>) -> Self {
         Self {
             alphabet,
             states: HashMap::new(),
@@ -17,7 +33,7 @@ impl ENAS {
         }
     }
 
-    pub fn add_state(&mut self, node: ENASNode) {
+    pub fn add_state(&mut self, node: ENASNode<S>) {
         let name = node.get_name().to_string();
         self.states.insert(name, node);
     }
@@ -26,34 +42,52 @@ impl ENAS {
         self.start_state = name.to_string();
     }
 
-    pub fn process(&self, input: &str) -> bool {
-        let mut current_states = self.epsilon_closure(&[self.start_state.clone()]);
-        for c in input.chars() {
-            let mut next_states = HashSet::new();
-            for state in &current_states {
-                if let Some(node) = self.states.get(state) {
-                    if let Some(targets) = node.get_connections().get(&c) {
-                        for target in targets {
-                            next_states.insert(target.clone());
-                        }
-                    }
+    pub fn alphabet(&self) -> &Alphabet<S> {
+        &self.alphabet
+    }
+
+    pub fn get_states(&self) -> &HashMap<String, ENASNode<S>> {
+        &self.states
+    }
+
+    pub fn get_start_state(&self) -> &str {
+        &self.start_state
+    }
+
+    pub fn accepts(&self, input: impl IntoIterator<Item = S>) -> bool {
+        let mut current_states = self.epsilon_closure(std::slice::from_ref(&self.start_state));
+        for c in input {
+            current_states = self.step(&current_states, &c);
+        }
+        self.any_accepting(&current_states)
+    }
+
+    // Zbiór stanów po przeczytaniu znaku `c` (wraz z domknięciem ε)
+    pub(crate) fn step(&self, current_states: &HashSet<String>, c: &S) -> HashSet<String> {
+        let label = Label::Symbol(c.clone());
+        let mut next_states = HashSet::new();
+        for state in current_states {
+            if let Some(targets) = self.states.get(state).and_then(|n| n.get_connections().get(&label)) {
+                for target in targets {
+                    next_states.insert(target.clone());
                 }
             }
-            current_states = self.epsilon_closure(&next_states.into_iter().collect::<Vec<_>>());
         }
-        current_states.iter().any(|s| self.states.get(s).map_or(false, |n| n.is_accepting()))
+        self.epsilon_closure(&next_states.into_iter().collect::<Vec<_>>())
     }
 
-    fn epsilon_closure(&self, states: &[String]) -> HashSet<String> {
+    pub(crate) fn any_accepting(&self, states: &HashSet<String>) -> bool {
+        states.iter().any(|s| self.states.get(s).is_some_and(|n| n.is_accepting()))
+    }
+
+    pub(crate) fn epsilon_closure(&self, states: &[String]) -> HashSet<String> {
         let mut closure: HashSet<String> = states.iter().cloned().collect();
         let mut queue: VecDeque<String> = states.iter().cloned().collect();
         while let Some(state) = queue.pop_front() {
-            if let Some(node) = self.states.get(&state) {
-                if let Some(epsilon_targets) = node.get_connections().get(&'ε') {
-                    for target in epsilon_targets {
-                        if closure.insert(target.clone()) {
-                            queue.push_back(target.clone());
-                        }
+            if let Some(epsilon_targets) = self.states.get(&state).and_then(|n| n.get_connections().get(&Label::Epsilon)) {
+                for target in epsilon_targets {
+                    if closure.insert(target.clone()) {
+                        queue.push_back(target.clone());
                     }
                 }
             }
@@ -61,26 +95,434 @@ impl ENAS {
         closure
     }
 
-    pub fn validate(&self) -> Vec<String> {
+    pub fn validate(&self) -> Vec<ValidationError<Label<S>>> {
         let mut errors = Vec::new();
         let state_names: HashSet<_> = self.states.keys().cloned().collect();
 
+        for symbol in self.alphabet.iter().filter(|s| s.is_reserved()) {
+            errors.push(ValidationError::ReservedSymbol {
+                symbol: Label::Symbol(symbol.clone()),
+            });
+        }
+
         for (state, node) in &self.states {
-            for (symbol, targets) in node.get_connections() {
-                let symbol_str = if *symbol == 'ε' { "ε".to_string() } else { symbol.to_string() };
+            for (label, targets) in node.get_connections() {
                 for target in targets {
                     if !state_names.contains(target) {
-                        errors.push(format!(
-                            "Stan '{}' ma połączenie dla '{}' do nieistniejącego stanu '{}'.",
-                            state,
-                            symbol_str,
-                            target
-                        ));
+                        errors.push(ValidationError::UnknownTarget {
+                            state: state.clone(),
+                            symbol: label.clone(),
+                            target: target.clone(),
+                        });
                     }
                 }
             }
         }
+        errors.sort();
         errors
     }
+
+    // Stany osiągalne ze stanu startowego (również przez przejścia ε)
+    pub fn reachable_states(&self) -> HashSet<String> {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        if self.states.contains_key(&self.start_state) {
+            visited.insert(self.start_state.clone());
+            queue.push_back(self.start_state.as_str());
+        }
+        while let Some(state) = queue.pop_front() {
+            for target in self.states[state].get_connections().values().flatten() {
+                if self.states.contains_key(target) && visited.insert(target.clone()) {
+                    queue.push_back(target);
+                }
+            }
+        }
+        visited
+    }
+
+    // Stany, z których da się dojść do stanu akceptującego
+    pub fn coreachable_states(&self) -> HashSet<String> {
+        let mut reverse: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (name, node) in &self.states {
+            for target in node.get_connections().values().flatten() {
+                reverse.entry(target.as_str()).or_default().push(name.as_str());
+            }
+        }
+
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        for (name, node) in &self.states {
+            if node.is_accepting() {
+                visited.insert(name.clone());
+                queue.push_back(name.as_str());
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            for &source in reverse.get(state).into_iter().flatten() {
+                if visited.insert(source.to_string()) {
+                    queue.push_back(source);
+                }
+            }
+        }
+        visited
+    }
+
+    // Automat bez stanów nieosiągalnych i martwych
+    pub fn trim(&self) -> ENAS<S> {
+        let reachable = self.reachable_states();
+        let coreachable = self.coreachable_states();
+        let useful = |name: &String| reachable.contains(name) && coreachable.contains(name);
+
+        let mut trimmed = ENAS::new(self.alphabet.clone());
+        trimmed.set_start_state(&self.start_state);
+        for (name, node) in self.states.iter().filter(|(name, _)| useful(name)) {
+            let mut trimmed_node = ENASNode::new(name, node.is_accepting());
+            trimmed_node.set_position(node.get_position());
+            for (label, targets) in node.get_connections() {
+                let targets: Vec<String> = targets.iter().filter(|t| useful(t)).cloned().collect();
+                if !targets.is_empty() {
+                    trimmed_node.add_connection(label.clone(), targets);
+                }
+            }
+            trimmed.add_state(trimmed_node);
+        }
+        trimmed
+    }
+
+    // Ten sam automat jako (być może niepełny) DAS – bez determinizacji, więc
+    // None, gdy są przejścia ε albo kilka stanów docelowych dla jednego symbolu
+    pub fn to_das(&self) -> Option<DAS<S>> {
+        let mut das = DAS::new(self.alphabet.clone());
+        das.set_start_state(&self.start_state);
+        for (name, node) in &self.states {
+            let mut das_node = DASNode::new(name, node.is_accepting());
+            das_node.set_position(node.get_position());
+            for (label, targets) in node.get_connections() {
+                match (label, targets.as_slice()) {
+                    (_, []) => {}
+                    (Label::Symbol(symbol), [target]) => das_node.add_connection(symbol.clone(), target),
+                    _ => return None,
+                }
+            }
+            das.add_state(das_node);
+        }
+        Some(das)
+    }
+
+    fn graph(&self) -> LabeledGraph<'_, S> {
+        let mut graph = LabeledGraph::new();
+        for (name, node) in &self.states {
+            for (label, targets) in node.get_connections() {
+                let label = label.symbol().cloned();
+                for target in targets {
+                    graph.add_edge(name, label.clone(), target);
+                }
+            }
+        }
+        graph
+    }
+
+    // Czy język jest pusty; świadek: przykładowe akceptowane słowo
+    pub fn is_empty(&self) -> Verdict<S> {
+        let accepting = |s: &str| self.states.get(s).is_some_and(|n| n.is_accepting());
+        match self.graph().shortest_word(&self.start_state, accepting) {
+            Some(word) => Verdict::new(false, Some(Witness::Accepted(word))),
+            None => Verdict::new(true, None),
+        }
+    }
+
+    // Czy automat akceptuje każde słowo nad alfabetem; przeszukuje wszerz
+    // zbiory stanów (determinizacja w locie), świadek: najkrótsze odrzucane słowo
+    pub fn is_universal(&self) -> Verdict<S> {
+        let symbols = self.alphabet.symbols();
+        let rejected = |word: Vec<S>| Verdict::new(false, Some(Witness::Rejected(word)));
+
+        let start = self.epsilon_closure(std::slice::from_ref(&self.start_state));
+        if !self.any_accepting(&start) {
+            return rejected(Vec::new());
+        }
+        let mut visited: HashSet<BTreeSet<String>> = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(start.iter().cloned().collect());
+        queue.push_back((start, Vec::new()));
+        while let Some((states, word)) = queue.pop_front() {
+            for symbol in &symbols {
+                let next = self.step(&states, symbol);
+                let mut next_word = word.clone();
+                next_word.push(symbol.clone());
+                if !self.any_accepting(&next) {
+                    return rejected(next_word);
+                }
+                if visited.insert(next.iter().cloned().collect()) {
+                    queue.push_back((next, next_word));
+                }
+            }
+        }
+        Verdict::new(true, None)
+    }
+
+    // Czy język jest skończony – szuka w przyciętym automacie cyklu, który
+    // czyta co najmniej jeden znak; świadek: pompowalny cykl
+    pub fn is_finite(&self) -> Verdict<S> {
+        let trimmed = self.trim();
+        let accepting: HashSet<String> = trimmed
+            .states
+            .values()
+            .filter(|n| n.is_accepting())
+            .map(|n| n.get_name().to_string())
+            .collect();
+        match trimmed.graph().pumpable_cycle(&trimmed.start_state, &accepting) {
+            Some(cycle) => Verdict::new(false, Some(cycle)),
+            None => Verdict::new(true, None),
+        }
+    }
+
+    pub fn accepts_epsilon(&self) -> Verdict<S> {
+        if self.accepts([]) {
+            Verdict::new(true, Some(Witness::Accepted(Vec::new())))
+        } else {
+            Verdict::new(false, Some(Witness::Rejected(Vec::new())))
+        }
+    }
+
+    // Słowa akceptowane w porządku shortlex, o długości co najwyżej `max_len`.
+    // Ze zbiorów stanów usuwane są stany martwe, a puste zbiory odcinane.
+    pub fn accepted_words(&self, max_len: usize) -> LanguageIter<'_, HashSet<String>, S> {
+        let coreachable = self.coreachable_states();
+        let alive = move |states: HashSet<String>| -> Option<HashSet<String>> {
+            let states: HashSet<String> = states.into_iter().filter(|s| coreachable.contains(s)).collect();
+            Some(states).filter(|s| !s.is_empty())
+        };
+        let start = alive(self.epsilon_closure(std::slice::from_ref(&self.start_state)));
+        LanguageIter::new(
+            start,
+            self.alphabet.symbols(),
+            max_len,
+            move |states, c| alive(self.step(states, c)),
+            |states| self.any_accepting(states),
+        )
+    }
+
+    // Słowa odrzucane w porządku shortlex, o długości co najwyżej `max_len`
+    pub fn rejected_words(&self, max_len: usize) -> LanguageIter<'_, HashSet<String>, S> {
+        LanguageIter::new(
+            Some(self.epsilon_closure(std::slice::from_ref(&self.start_state))),
+            self.alphabet.symbols(),
+            max_len,
+            |states, c| Some(self.step(states, c)),
+            |states| !self.any_accepting(states),
+        )
+    }
+
+    // Konstrukcja podzbiorów: każdy stan DAS to domknięcie ε zbioru stanów,
+    // nazwane np. "{q0,q1}". Pusty zbiór jest pomijany, więc wynikowy DAS
+    // może być niepełny.
+    pub fn determinize(&self) -> DAS<S> {
+        let symbols = self.alphabet.symbols();
+
+        let start: BTreeSet<String> = self
+            .epsilon_closure(std::slice::from_ref(&self.start_state))
+            .into_iter()
+            .collect();
+        let mut das = DAS::new(self.alphabet.clone());
+        das.set_start_state(&subset_name(&start));
+
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(start.clone());
+        queue.push_back(start);
+        while let Some(set) = queue.pop_front() {
+            let states: HashSet<String> = set.iter().cloned().collect();
+            let mut node = DASNode::new(&subset_name(&set), self.any_accepting(&states));
+            for symbol in &symbols {
+                let next: BTreeSet<String> = self.step(&states, symbol).into_iter().collect();
+                if next.is_empty() {
+                    continue;
+                }
+                node.add_connection(symbol.clone(), &subset_name(&next));
+                if visited.insert(next.clone()) {
+                    queue.push_back(next);
+                }
+            }
+            das.add_state(node);
+        }
+        das
+    }
+
+    // Liczenie słów wymaga determinizacji – w ε-NAS jedno słowo może mieć
+    // wiele ścieżek akceptujących
+    pub fn count_words(&self, n: usize) -> BigUint {
+        self.determinize().count_words(n)
+    }
+
+    pub fn count_words_up_to(&self, n: usize) -> Vec<BigUint> {
+        self.determinize().count_words_up_to(n)
+    }
+
+    pub fn recurrence(&self) -> Recurrence {
+        self.determinize().recurrence()
+    }
+
+    pub fn generating_function(&self) -> GeneratingFunction {
+        self.determinize().generating_function()
+    }
+}
+
+// Nazwa stanu DAS dla zbioru stanów, np. "{q0,q1}". Przecinki, nawiasy klamrowe
+// i ukośniki w nazwach stanów są poprzedzane ukośnikiem, więc różne zbiory
+// zawsze dostają różne nazwy.
+fn subset_name(set: &BTreeSet<String>) -> String {
+    let members: Vec<String> = set
+        .iter()
+        .map(|state| {
+            let mut escaped = String::new();
+            for c in state.chars() {
+                if matches!(c, ',' | '{' | '}' | '\\') {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+            }
+            escaped
+        })
+        .collect();
+    format!("{{{}}}", members.join(","))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subset_names_do_not_collide() {
+        let mut enas = ENAS::new(Alphabet::new());
+        for name in ["s", "a,b", "c", "a", "b,c"] {
+            enas.add_state(ENASNode::new(name, false));
+        }
+        enas.alphabet.add('x');
+        enas.alphabet.add('y');
+        let mut start = ENASNode::new("s", false);
+        start.add_connection(Label::Symbol('x'), vec!["a,b".to_string(), "c".to_string()]);
+        start.add_connection(Label::Symbol('y'), vec!["a".to_string(), "b,c".to_string()]);
+        enas.add_state(start);
+        enas.set_start_state("s");
+
+        let das = enas.determinize();
+        assert_eq!(das.get_states().len(), 3);
+        assert!(das.get_states().contains_key("{a\\,b,c}"));
+        assert!(das.get_states().contains_key("{a,b\\,c}"));
+    }
+
+    fn enas(text: &str) -> ENAS {
+        crate::formats::Dsl::parse(text).unwrap().to_enas()
+    }
+
+    fn names(states: impl IntoIterator<Item = String>) -> Vec<String> {
+        let mut names: Vec<String> = states.into_iter().collect();
+        names.sort();
+        names
+    }
+
+    fn language(enas: &ENAS) -> Vec<String> {
+        enas.accepted_words(6).map(|w| w.into_iter().collect()).collect()
+    }
+
+    #[test]
+    fn trim_follows_epsilon_transitions() {
+        // q1 osiągalny tylko przez ε, q3 nieosiągalny, q4 martwy
+        let automat = enas("start q0\naccept q2\nq0 -ε-> q1\nq1 -a-> q2, q4\nq3 -b-> q2\nq4 -b-> q4");
+        assert_eq!(names(automat.reachable_states()), ["q0", "q1", "q2", "q4"]);
+        assert_eq!(names(automat.coreachable_states()), ["q0", "q1", "q2", "q3"]);
+        let trimmed = automat.trim();
+        assert_eq!(names(trimmed.get_states().keys().cloned()), ["q0", "q1", "q2"]);
+        assert_eq!(trimmed.get_states()["q1"].get_connections()[&Label::Symbol('a')], ["q2"]);
+        assert_eq!(language(&trimmed), language(&automat));
+    }
+
+    #[test]
+    fn trim_of_empty_language() {
+        let trimmed = enas("start q0\naccept q2\nq0 -ε-> q1\nq1 -a-> q1").trim();
+        assert!(trimmed.get_states().is_empty());
+        assert_eq!(trimmed.get_start_state(), "q0");
+        assert!(!trimmed.accepts("".chars()));
+    }
+
+    // Sprawdza, czy świadek naprawdę potwierdza werdykt
+    fn check_witness(enas: &ENAS, witness: &Witness) {
+        match witness {
+            Witness::Accepted(word) => assert!(enas.accepts(word.iter().copied())),
+            Witness::Rejected(word) => assert!(!enas.accepts(word.iter().copied())),
+            Witness::Cycle { prefix, cycle, suffix } => {
+                assert!(!cycle.is_empty());
+                for n in 0..4 {
+                    let word = prefix.iter().chain(cycle.iter().cycle().take(n * cycle.len())).chain(suffix);
+                    assert!(enas.accepts(word.copied()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn witnesses_are_valid() {
+        let automat = enas("alphabet a b\nstart q0\naccept q2\nq0 -a,b-> q0\nq0 -a-> q1\nq1 -b-> q2");
+        let verdicts = [automat.is_empty(), automat.is_universal(), automat.is_finite(), automat.accepts_epsilon()];
+        for verdict in verdicts {
+            assert!(!verdict.holds);
+            check_witness(&automat, &verdict.witness.unwrap());
+        }
+        assert_eq!(automat.is_empty().witness, Some(Witness::Accepted(vec!['a', 'b'])));
+    }
+
+    #[test]
+    fn witnesses_of_holding_properties() {
+        let universal = enas("alphabet a b\nstart q0\naccept q1\nq0 -ε-> q1\nq1 -a,b-> q0");
+        assert_eq!(universal.is_universal(), Verdict::new(true, None));
+        let verdict = universal.accepts_epsilon();
+        assert!(verdict.holds);
+        check_witness(&universal, &verdict.witness.unwrap());
+
+        let finite = enas("alphabet a b\nstart q0\naccept q2\nq0 -ε-> q1\nq1 -a-> q2\nq2 -b-> q3");
+        assert_eq!(finite.is_finite(), Verdict::new(true, None));
+        let verdict = finite.is_universal();
+        assert!(!verdict.holds);
+        check_witness(&finite, &verdict.witness.unwrap());
+
+        let empty = enas("start q0\naccept q2\nq0 -ε-> q1\nq1 -a-> q1");
+        assert_eq!(empty.is_empty(), Verdict::new(true, None));
+    }
+
+    #[test]
+    fn cycle_closed_by_epsilon_is_pumpable() {
+        // Jedyny cykl q0 -a-> q1 -ε-> q0 wraca do początku ruchem ε
+        let automat = enas("alphabet a b\nstart q0\naccept q2\nq0 -a-> q1\nq1 -ε-> q0\nq1 -b-> q2");
+        let verdict = automat.is_finite();
+        assert!(!verdict.holds);
+        let witness = verdict.witness.unwrap();
+        assert!(matches!(&witness, Witness::Cycle { cycle, .. } if cycle == &['a']));
+        check_witness(&automat, &witness);
+        assert_eq!(language(&automat), ["ab", "aab", "aaab", "aaaab", "aaaaab"]);
+    }
+
+    #[test]
+    fn reserved_symbol_is_separate_from_epsilon() {
+        // Symbol 'ε' w alfabecie jest błędem, ale jego przejście nie miesza się z ruchem ε
+        let mut alphabet = Alphabet::new();
+        alphabet.add('a');
+        alphabet.add('ε');
+        let mut automat = ENAS::new(alphabet);
+        let mut start = ENASNode::new("q0", false);
+        start.add_connection(Label::Symbol('ε'), vec!["q1".to_string()]);
+        start.add_connection(Label::Epsilon, vec!["q2".to_string()]);
+        automat.add_state(start);
+        automat.add_state(ENASNode::new("q1", true));
+        automat.add_state(ENASNode::new("q2", false));
+        automat.set_start_state("q0");
+
+        assert_eq!(automat.validate(), [ValidationError::ReservedSymbol { symbol: Label::Symbol('ε') }]);
+        let connections = automat.get_states()["q0"].get_connections();
+        assert_eq!(connections.len(), 2);
+        assert_eq!(connections[&Label::Symbol('ε')], ["q1"]);
+        assert_eq!(connections[&Label::Epsilon], ["q2"]);
+        assert!(automat.accepts("ε".chars()));
+        assert!(!automat.accepts("".chars()));
+    }
+}
@@ -0,0 +1,69 @@
+use crate::automats::Verdict::Witness;
+use crate::elements::Symbol::Symbol;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// Graf przejść automatu z etykietami; etykieta None oznacza przejście ε.
+// Wspólne algorytmy dla DAS i ENAS.
+pub(crate) struct LabeledGraph<'a, S> {
+    edges: HashMap<&'a str, Vec<(Option<S>, &'a str)>>,
+}
+
+impl<'a, S: Symbol> LabeledGraph<'a, S> {
+    pub(crate) fn new() -> Self {
+        Self { edges: HashMap::new() }
+    }
+
+    pub(crate) fn add_edge(&mut self, from: &'a str, label: Option<S>, to: &'a str) {
+        let edges = self.edges.entry(from).or_default();
+        edges.push((label, to));
+        // Stała kolejność krawędzi daje powtarzalnych świadków
+        edges.sort();
+    }
+
+    // Najkrótsza (w liczbie krawędzi) ścieżka z `from` do stanu spełniającego
+    // `goal`, zapisana jako słowo (przejścia ε nie dokładają symboli)
+    pub(crate) fn shortest_word(&self, from: &str, goal: impl Fn(&str) -> bool) -> Option<Vec<S>> {
+        let mut parent: HashMap<&str, Option<(&str, &Option<S>)>> = HashMap::new();
+        let mut queue = VecDeque::new();
+        parent.insert(from, None);
+        queue.push_back(from);
+        while let Some(state) = queue.pop_front() {
+            if goal(state) {
+                let mut labels: Vec<S> = Vec::new();
+                let mut current = state;
+                while let Some(Some((prev, label))) = parent.get(current) {
+                    labels.extend((*label).clone());
+                    current = prev;
+                }
+                labels.reverse();
+                return Some(labels);
+            }
+            for (label, target) in self.edges.get(state).into_iter().flatten() {
+                if !parent.contains_key(target) {
+                    parent.insert(target, Some((state, label)));
+                    queue.push_back(target);
+                }
+            }
+        }
+        None
+    }
+
+    // Szuka cyklu zawierającego przejście po symbolu, leżącego na drodze ze stanu
+    // startowego do stanu akceptującego. Graf powinien być przycięty (`trim`).
+    pub(crate) fn pumpable_cycle(&self, start: &str, accepting: &HashSet<String>) -> Option<Witness<S>> {
+        let mut sources: Vec<&str> = self.edges.keys().copied().collect();
+        sources.sort();
+        for from in sources {
+            for (label, to) in &self.edges[from] {
+                let Some(symbol) = label else { continue };
+                let Some(back) = self.shortest_word(to, |s| s == from) else { continue };
+                let prefix = self.shortest_word(start, |s| s == from)?;
+                let suffix = self.shortest_word(from, |s| accepting.contains(s))?;
+                let mut cycle = vec![symbol.clone()];
+                cycle.extend(back);
+                return Some(Witness::Cycle { prefix, cycle, suffix });
+            }
+        }
+        None
+    }
+}
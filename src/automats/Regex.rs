@@ -0,0 +1,294 @@
+use crate::elements::Alphabet::Alphabet;
+use crate::elements::Label::Label;
+use crate::elements::Node::ENASNode;
+use crate::automats::ENAS::ENAS;
+use std::collections::{BTreeSet, HashMap};
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+// Znaki, które obejmuje `.` oraz klasy zanegowane `[^...]` – alfabet
+// automatu musi być skończony, więc są to drukowalne znaki ASCII i białe znaki
+fn universe() -> BTreeSet<char> {
+    let mut chars: BTreeSet<char> = (' '..='~').collect();
+    chars.extend(['\t', '\n', '\r']);
+    chars
+}
+
+// Drzewo składniowe wyrażenia regularnego
+enum Regex {
+    Empty,
+    Chars(BTreeSet<char>),
+    Concat(Vec<Regex>),
+    Alternative(Vec<Regex>),
+    Star(Box<Regex>),
+    Plus(Box<Regex>),
+    Optional(Box<Regex>),
+}
+
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    len: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn position(&mut self) -> usize {
+        self.chars.peek().map_or(self.len, |&(i, _)| i)
+    }
+
+    fn error<T>(&mut self, message: &str) -> Result<T, String> {
+        Err(format!("{} (pozycja {})", message, self.position() + 1))
+    }
+
+    // alternatywa := konkatenacja ('|' konkatenacja)*
+    fn alternative(&mut self) -> Result<Regex, String> {
+        let mut options = vec![self.concat()?];
+        while self.chars.next_if(|&(_, c)| c == '|').is_some() {
+            options.push(self.concat()?);
+        }
+        Ok(if options.len() == 1 { options.pop().unwrap() } else { Regex::Alternative(options) })
+    }
+
+    // konkatenacja := powtórzenie*
+    fn concat(&mut self) -> Result<Regex, String> {
+        let mut parts = Vec::new();
+        while let Some(&(_, c)) = self.chars.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            parts.push(self.repeat()?);
+        }
+        Ok(match parts.len() {
+            0 => Regex::Empty,
+            1 => parts.pop().unwrap(),
+            _ => Regex::Concat(parts),
+        })
+    }
+
+    // powtórzenie := atom ('*' | '+' | '?')*
+    fn repeat(&mut self) -> Result<Regex, String> {
+        let mut regex = self.atom()?;
+        while let Some((_, c)) = self.chars.next_if(|&(_, c)| matches!(c, '*' | '+' | '?')) {
+            regex = match c {
+                '*' => Regex::Star(Box::new(regex)),
+                '+' => Regex::Plus(Box::new(regex)),
+                _ => Regex::Optional(Box::new(regex)),
+            };
+        }
+        Ok(regex)
+    }
+
+    fn atom(&mut self) -> Result<Regex, String> {
+        let Some((i, c)) = self.chars.next() else {
+            return self.error("Nieoczekiwany koniec wyrażenia");
+        };
+        match c {
+            '(' => {
+                let regex = self.alternative()?;
+                if self.chars.next_if(|&(_, c)| c == ')').is_none() {
+                    return self.error("Brak nawiasu zamykającego ')'");
+                }
+                Ok(regex)
+            }
+            '[' => self.class(),
+            '.' => {
+                let mut chars = universe();
+                chars.remove(&'\n');
+                Ok(Regex::Chars(chars))
+            }
+            '\\' => Ok(Regex::Chars(self.escape()?)),
+            '*' | '+' | '?' => Err(format!("Operator '{}' nie ma argumentu (pozycja {})", c, i + 1)),
+            c => Ok(Regex::Chars(BTreeSet::from([c]))),
+        }
+    }
+
+    // Sekwencja po '\': \n, \t, \r, klasy \d, \w, \s albo dosłowny znak
+    fn escape(&mut self) -> Result<BTreeSet<char>, String> {
+        let Some((_, c)) = self.chars.next() else {
+            return self.error("Niedokończona sekwencja '\\'");
+        };
+        Ok(match c {
+            'n' => BTreeSet::from(['\n']),
+            't' => BTreeSet::from(['\t']),
+            'r' => BTreeSet::from(['\r']),
+            'd' => ('0'..='9').collect(),
+            'w' => ('a'..='z').chain('A'..='Z').chain('0'..='9').chain(['_']).collect(),
+            's' => BTreeSet::from([' ', '\t', '\n', '\r']),
+            c => BTreeSet::from([c]),
+        })
+    }
+
+    // Klasa znaków po '[': znaki, zakresy a-z, sekwencje '\' i negacja '^'
+    fn class(&mut self) -> Result<Regex, String> {
+        let negated = self.chars.next_if(|&(_, c)| c == '^').is_some();
+        let mut chars = BTreeSet::new();
+        let mut first = true;
+        loop {
+            let Some((_, c)) = self.chars.next() else {
+                return self.error("Brak nawiasu zamykającego ']'");
+            };
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+            if c == '\\' {
+                chars.extend(self.escape()?);
+                continue;
+            }
+            let is_range = self.chars.peek().is_some_and(|&(_, d)| d == '-')
+                && self.chars.clone().nth(1).is_some_and(|(_, d)| d != ']');
+            if is_range {
+                self.chars.next();
+                let (_, end) = self.chars.next().unwrap();
+                if end < c {
+                    return self.error(&format!("Odwrócony zakres '{}-{}'", c, end));
+                }
+                if end as u32 - c as u32 >= Alphabet::MAX_RANGE {
+                    let message = format!("Zakres '{}-{}' jest zbyt duży – najwyżej {} znaków", c.escape_debug(), end.escape_debug(), Alphabet::MAX_RANGE);
+                    return self.error(&message);
+                }
+                chars.extend(c..=end);
+            } else {
+                chars.insert(c);
+            }
+        }
+        if negated {
+            chars = universe().difference(&chars).copied().collect();
+        }
+        Ok(Regex::Chars(chars))
+    }
+}
+
+// Konstrukcja Thompsona: każdy fragment ma jeden stan wejściowy i jeden wyjściowy.
+// Kolejność krawędzi ε wyznacza priorytet ścieżek przy wyszukiwaniu
+// `LeftmostFirst`: alternatywy w kolejności zapisu, powtórzenia zachłanne
+// (kolejny obrót pętli przed wyjściem).
+#[derive(Default)]
+struct Builder {
+    edges: Vec<HashMap<Label<char>, Vec<usize>>>,
+}
+
+impl Builder {
+    fn state(&mut self) -> usize {
+        self.edges.push(HashMap::new());
+        self.edges.len() - 1
+    }
+
+    fn edge(&mut self, from: usize, label: Label<char>, to: usize) {
+        self.edges[from].entry(label).or_default().push(to);
+    }
+
+    fn fragment(&mut self, regex: &Regex) -> (usize, usize) {
+        let (start, end) = (self.state(), self.state());
+        match regex {
+            Regex::Empty => self.edge(start, Label::Epsilon, end),
+            Regex::Chars(chars) => {
+                for &c in chars {
+                    self.edge(start, Label::Symbol(c), end);
+                }
+            }
+            Regex::Concat(parts) => {
+                let mut current = start;
+                for part in parts {
+                    let (s, e) = self.fragment(part);
+                    self.edge(current, Label::Epsilon, s);
+                    current = e;
+                }
+                self.edge(current, Label::Epsilon, end);
+            }
+            Regex::Alternative(options) => {
+                for option in options {
+                    let (s, e) = self.fragment(option);
+                    self.edge(start, Label::Epsilon, s);
+                    self.edge(e, Label::Epsilon, end);
+                }
+            }
+            Regex::Star(inner) | Regex::Plus(inner) | Regex::Optional(inner) => {
+                let (s, e) = self.fragment(inner);
+                self.edge(start, Label::Epsilon, s);
+                if !matches!(regex, Regex::Plus(_)) {
+                    self.edge(start, Label::Epsilon, end);
+                }
+                if !matches!(regex, Regex::Optional(_)) {
+                    self.edge(e, Label::Epsilon, s);
+                }
+                self.edge(e, Label::Epsilon, end);
+            }
+        }
+        (start, end)
+    }
+}
+
+impl ENAS<char> {
+    // ε-NAS rozpoznający język wyrażenia regularnego. Obsługiwane są: znaki,
+    // '.', klasy [a-z0-9] i [^...], sekwencje \n \t \r \d \w \s, nawiasy,
+    // alternatywa '|' oraz operatory '*', '+', '?'.
+    pub fn from_regex(pattern: &str) -> Result<ENAS<char>, String> {
+        let mut parser = Parser {
+            chars: pattern.char_indices().peekable(),
+            len: pattern.len(),
+        };
+        let regex = parser.alternative()?;
+        if parser.chars.peek().is_some() {
+            return parser.error("Nieoczekiwany znak ')'");
+        }
+
+        let mut builder = Builder::default();
+        let (start, end) = builder.fragment(&regex);
+        let mut alphabet = Alphabet::new();
+        let name = |i: usize| format!("s{}", i);
+        let mut enas = ENAS::new(Alphabet::new());
+        for (i, edges) in builder.edges.into_iter().enumerate() {
+            let mut node = ENASNode::new(&name(i), i == end);
+            for (label, targets) in edges {
+                if let Label::Symbol(c) = label {
+                    alphabet.add(c);
+                }
+                node.add_connection(label, targets.into_iter().map(name).collect());
+            }
+            enas.add_state(node);
+        }
+        enas.alphabet = alphabet;
+        enas.set_start_state(&name(start));
+        Ok(enas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accepted(pattern: &str, words: &[&str]) -> Vec<bool> {
+        let enas = ENAS::from_regex(pattern).unwrap();
+        words.iter().map(|w| enas.accepts(w.chars())).collect()
+    }
+
+    #[test]
+    fn operators() {
+        assert_eq!(accepted("ab|c", &["ab", "c", "a", "abc"]), [true, true, false, false]);
+        assert_eq!(accepted("a(b|c)*d", &["ad", "abcbd", "abd?", "a"]), [true, true, false, false]);
+        assert_eq!(accepted("a+b?", &["a", "aab", "b", "abb"]), [true, true, false, false]);
+        assert_eq!(accepted("", &["", "a"]), [true, false]);
+    }
+
+    #[test]
+    fn classes_and_escapes() {
+        assert_eq!(accepted("[a-c_]+", &["ab_c", "d"]), [true, false]);
+        assert_eq!(accepted("[^0-9]", &["x", "5", "\n"]), [true, false, true]);
+        assert_eq!(accepted("\\d+\\.\\d", &["12.5", "1x5"]), [true, false]);
+        assert_eq!(accepted("\\w\\s\\*", &["_ *", "a\t*", "a b"]), [true, true, false]);
+        assert_eq!(accepted(".", &["a", "\n"]), [true, false]);
+    }
+
+    #[test]
+    fn errors_report_position() {
+        assert_eq!(ENAS::from_regex("(ab").err().unwrap(), "Brak nawiasu zamykającego ')' (pozycja 4)");
+        assert_eq!(ENAS::from_regex("a|*").err().unwrap(), "Operator '*' nie ma argumentu (pozycja 3)");
+        assert!(ENAS::from_regex("[z-a]").is_err());
+    }
+
+    #[test]
+    fn class_range_size_is_capped() {
+        assert!(ENAS::from_regex("[\u{0}-\u{10FFFF}]").is_err());
+        assert!(ENAS::from_regex("[а-я]+").unwrap().accepts("привет".chars()));
+    }
+}
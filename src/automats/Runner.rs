@@ -0,0 +1,187 @@
+use crate::elements::Symbol::Symbol;
+use crate::automats::DAS::DAS;
+use crate::automats::ENAS::ENAS;
+use std::collections::HashSet;
+
+// Wspólny interfejs automatu czytającego wejście porcjami (np. z gniazda
+// albo pliku). Stan jest pamiętany między wywołaniami `feed`.
+pub trait Runner<S: Symbol> {
+    // Czyta kolejną porcję symboli. Gdy automat stanie się martwy, reszta
+    // porcji jest pomijana. Zwraca false, jeśli automat jest martwy.
+    fn feed(&mut self, chunk: impl IntoIterator<Item = S>) -> bool;
+    // Czy dotychczas przeczytane wejście jest akceptowane
+    fn is_accepting(&self) -> bool;
+    // Czy żadne przedłużenie wejścia nie może już zostać zaakceptowane
+    fn is_dead(&self) -> bool;
+    // Liczba przeczytanych symboli (bez pominiętych po śmierci automatu)
+    fn consumed(&self) -> usize;
+    // Powrót do stanu startowego
+    fn reset(&mut self);
+}
+
+pub struct DasRunner<'a, S: Symbol = char> {
+    das: &'a DAS<S>,
+    coreachable: HashSet<String>,
+    // None: brak przejścia – słowo zostało odrzucone
+    current: Option<&'a str>,
+    consumed: usize,
+}
+
+impl<S: Symbol> DAS<S> {
+    pub fn start(&self) -> DasRunner<'_, S> {
+        DasRunner {
+            das: self,
+            coreachable: self.coreachable_states(),
+            current: Some(self.get_start_state()),
+            consumed: 0,
+        }
+    }
+}
+
+impl<'a, S: Symbol> DasRunner<'a, S> {
+    // Nazwa bieżącego stanu
+    pub fn state(&self) -> Option<&'a str> {
+        self.current
+    }
+}
+
+impl<S: Symbol> Runner<S> for DasRunner<'_, S> {
+    fn feed(&mut self, chunk: impl IntoIterator<Item = S>) -> bool {
+        for symbol in chunk {
+            if self.is_dead() {
+                return false;
+            }
+            self.current = self
+                .current
+                .and_then(|state| self.das.get_states().get(state))
+                .and_then(|node| node.get_connections().get(&symbol))
+                .map(|next| next.as_str());
+            self.consumed += 1;
+        }
+        !self.is_dead()
+    }
+
+    fn is_accepting(&self) -> bool {
+        self.current.is_some_and(|state| self.das.is_accepting_state(state))
+    }
+
+    fn is_dead(&self) -> bool {
+        self.current.is_none_or(|state| !self.coreachable.contains(state))
+    }
+
+    fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    fn reset(&mut self) {
+        self.current = Some(self.das.get_start_state());
+        self.consumed = 0;
+    }
+}
+
+pub struct EnasRunner<'a, S: Symbol = char> {
+    enas: &'a ENAS<S>,
+    coreachable: HashSet<String>,
+    // Bieżący zbiór stanów (domknięty ε), bez stanów martwych
+    current: HashSet<String>,
+    consumed: usize,
+}
+
+impl<S: Symbol> ENAS<S> {
+    pub fn start(&self) -> EnasRunner<'_, S> {
+        let mut runner = EnasRunner {
+            enas: self,
+            coreachable: self.coreachable_states(),
+            current: HashSet::new(),
+            consumed: 0,
+        };
+        runner.reset();
+        runner
+    }
+}
+
+impl<S: Symbol> EnasRunner<'_, S> {
+    // Bieżące stany, z których da się jeszcze dojść do akceptacji
+    pub fn states(&self) -> &HashSet<String> {
+        &self.current
+    }
+
+    fn set_current(&mut self, states: HashSet<String>) {
+        self.current = states.into_iter().filter(|s| self.coreachable.contains(s)).collect();
+    }
+}
+
+impl<S: Symbol> Runner<S> for EnasRunner<'_, S> {
+    fn feed(&mut self, chunk: impl IntoIterator<Item = S>) -> bool {
+        for symbol in chunk {
+            if self.is_dead() {
+                return false;
+            }
+            let next = self.enas.step(&self.current, &symbol);
+            self.set_current(next);
+            self.consumed += 1;
+        }
+        !self.is_dead()
+    }
+
+    fn is_accepting(&self) -> bool {
+        self.enas.any_accepting(&self.current)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.current.is_empty()
+    }
+
+    fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    fn reset(&mut self) {
+        let start = self.enas.epsilon_closure(std::slice::from_ref(&self.enas.get_start_state().to_string()));
+        self.set_current(start);
+        self.consumed = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn das_runner_keeps_state_between_chunks() {
+        let das = ENAS::from_regex("ab*c").unwrap().determinize();
+        let mut runner = das.start();
+        assert!(runner.feed("ab".chars()));
+        assert!(!runner.is_accepting());
+        assert!(runner.feed("bc".chars()));
+        assert!(runner.is_accepting());
+        assert_eq!(runner.consumed(), 4);
+    }
+
+    #[test]
+    fn das_runner_stops_when_dead() {
+        let das = ENAS::from_regex("ab*c").unwrap().determinize();
+        let mut runner = das.start();
+        // "x" zabija automat – reszta porcji jest pomijana
+        assert!(!runner.feed("acxyz".chars()));
+        assert!(runner.is_dead());
+        assert_eq!(runner.consumed(), 3);
+        runner.reset();
+        assert_eq!((runner.consumed(), runner.is_dead()), (0, false));
+        assert_eq!(runner.state(), Some(das.get_start_state()));
+    }
+
+    #[test]
+    fn enas_runner_follows_epsilon_transitions() {
+        let enas = ENAS::from_regex("(ab)*|c").unwrap();
+        let mut runner = enas.start();
+        assert!(runner.is_accepting());
+        assert!(runner.feed("a".chars()));
+        assert!(!runner.is_accepting());
+        assert!(runner.feed("bab".chars()));
+        assert!(runner.is_accepting());
+        assert!(!runner.feed("c".chars()));
+        assert!(runner.is_dead() && runner.states().is_empty());
+        assert_eq!(runner.consumed(), 5);
+    }
+}
@@ -0,0 +1,400 @@
+use crate::elements::Label::Label;
+use crate::elements::Node::NodeBase;
+use crate::elements::Symbol::{Symbol, Token};
+use crate::automats::DAS::DAS;
+use crate::automats::ENAS::ENAS;
+use crate::automats::Runner::{DasRunner, Runner};
+use std::collections::HashSet;
+use std::iter;
+use std::ops::Range;
+
+// Dopasowanie wzorca w tekście – zakres bajtów [start, end)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Match {
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+// Który z dopasowań zaczynających się najwcześniej wybrać
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MatchKind {
+    // Najdłuższe dopasowanie (jak w POSIX)
+    #[default]
+    LeftmostLongest,
+    // Dopasowanie najbardziej preferowanej ścieżki (jak w Perlu i PCRE):
+    // w automacie z wyrażenia regularnego wygrywa wcześniejsza alternatywa,
+    // a powtórzenia są zachłanne. W DAS każde słowo ma jedną ścieżkę, więc
+    // wynik jest taki sam jak dla `LeftmostLongest`.
+    LeftmostFirst,
+}
+
+// Tekst, w którym można szukać dopasowań automatu o symbolach `S`.
+// Każdy symbol ma swój zakres bajtów w tekście.
+pub trait Haystack<S> {
+    fn symbols(&self) -> Vec<(Range<usize>, S)>;
+}
+
+impl Haystack<char> for str {
+    fn symbols(&self) -> Vec<(Range<usize>, char)> {
+        self.char_indices().map(|(i, c)| (i..i + c.len_utf8(), c)).collect()
+    }
+}
+
+impl Haystack<u8> for [u8] {
+    fn symbols(&self) -> Vec<(Range<usize>, u8)> {
+        self.iter().enumerate().map(|(i, &b)| (i..i + 1, b)).collect()
+    }
+}
+
+// Tokeny to słowa oddzielone białymi znakami
+impl Haystack<Token> for str {
+    fn symbols(&self) -> Vec<(Range<usize>, Token)> {
+        let mut symbols = Vec::new();
+        let mut start = None;
+        for (i, c) in self.char_indices().chain(iter::once((self.len(), ' '))) {
+            match (start, c.is_whitespace()) {
+                (None, false) => start = Some(i),
+                (Some(s), true) => {
+                    symbols.push((s..i, Token::new(&self[s..i])));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        symbols
+    }
+}
+
+// Symulacja ENAS z priorytetami, jak wątki maszyny Pike'a: bieżące stany są
+// uporządkowane od najbardziej preferowanego. Priorytet wynika z kolejności
+// stanów docelowych na krawędziach; przejście dalej ze stanu akceptującego
+// jest preferowane nad zakończeniem w nim dopasowania. Przy `LeftmostFirst`
+// stany mniej preferowane niż akceptacja są odrzucane – późniejsze
+// dopasowanie może pochodzić już tylko ze ścieżki ważniejszej od niej.
+pub struct PriorityRunner<'a, S: Symbol = char> {
+    enas: &'a ENAS<S>,
+    kind: MatchKind,
+    coreachable: HashSet<String>,
+    current: Vec<&'a str>,
+    consumed: usize,
+}
+
+impl<'a, S: Symbol> PriorityRunner<'a, S> {
+    fn new(enas: &'a ENAS<S>, kind: MatchKind) -> Self {
+        let mut runner = Self {
+            enas,
+            kind,
+            coreachable: enas.coreachable_states(),
+            current: Vec::new(),
+            consumed: 0,
+        };
+        runner.reset();
+        runner
+    }
+
+    // Bieżące stany od najbardziej preferowanego
+    pub fn states(&self) -> &[&'a str] {
+        &self.current
+    }
+
+    // Dokłada do `next` stany osiągalne z `from` przez ε, w kolejności
+    // przeszukiwania w głąb. Zwraca true, gdy przy `LeftmostFirst` trafiła
+    // się akceptacja i dalsze (mniej preferowane) stany należy pominąć.
+    fn add(&self, from: &'a str, next: &mut Vec<&'a str>, seen: &mut HashSet<&'a str>) -> bool {
+        let enas: &'a ENAS<S> = self.enas;
+        // None oznacza koniec poddrzewa stanu akceptującego
+        let mut stack = vec![Some(from)];
+        while let Some(entry) = stack.pop() {
+            let Some(state) = entry else { return true };
+            if !seen.insert(state) || !self.coreachable.contains(state) {
+                continue;
+            }
+            next.push(state);
+            let node = &enas.get_states()[state];
+            if node.is_accepting() && self.kind == MatchKind::LeftmostFirst {
+                stack.push(None);
+            }
+            if let Some(targets) = node.get_connections().get(&Label::Epsilon) {
+                stack.extend(targets.iter().rev().map(|t| Some(t.as_str())));
+            }
+        }
+        false
+    }
+}
+
+impl<S: Symbol> Runner<S> for PriorityRunner<'_, S> {
+    fn feed(&mut self, chunk: impl IntoIterator<Item = S>) -> bool {
+        let enas = self.enas;
+        for symbol in chunk {
+            if self.is_dead() {
+                return false;
+            }
+            let label = Label::Symbol(symbol);
+            let mut next = Vec::new();
+            let mut seen = HashSet::new();
+            'states: for state in &self.current {
+                let Some(targets) = enas.get_states()[*state].get_connections().get(&label) else { continue };
+                for target in targets {
+                    if self.add(target, &mut next, &mut seen) {
+                        break 'states;
+                    }
+                }
+            }
+            self.current = next;
+            self.consumed += 1;
+        }
+        !self.is_dead()
+    }
+
+    fn is_accepting(&self) -> bool {
+        self.current.iter().any(|s| self.enas.get_states()[*s].is_accepting())
+    }
+
+    fn is_dead(&self) -> bool {
+        self.current.is_empty()
+    }
+
+    fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    fn reset(&mut self) {
+        let mut start = Vec::new();
+        if self.enas.get_states().contains_key(self.enas.get_start_state()) {
+            self.add(self.enas.get_start_state(), &mut start, &mut HashSet::new());
+        }
+        self.current = start;
+        self.consumed = 0;
+    }
+}
+
+// Iterator po kolejnych, rozłącznych dopasowaniach. Automat jest uruchamiany
+// od nowa na każdej pozycji startowej, więc przejrzenie tekstu długości n
+// kosztuje w najgorszym razie O(n²) kroków (np. wzorzec a*b w tekście z samych a).
+pub struct Matches<S: Symbol, R: Runner<S>> {
+    runner: R,
+    symbols: Vec<(Range<usize>, S)>,
+    // Indeks symbolu, od którego zaczyna się kolejne wyszukiwanie
+    next: usize,
+    // Koniec tekstu – pozycja pustego dopasowania za ostatnim symbolem
+    len: usize,
+    // Koniec poprzedniego dopasowania; puste dopasowanie tuż za nim jest pomijane
+    last_end: Option<usize>,
+}
+
+impl<S: Symbol, R: Runner<S>> Matches<S, R> {
+    fn new(runner: R, symbols: Vec<(Range<usize>, S)>) -> Self {
+        let len = symbols.last().map_or(0, |(range, _)| range.end);
+        Self {
+            runner,
+            symbols,
+            next: 0,
+            len,
+            last_end: None,
+        }
+    }
+
+    fn offset(&self, index: usize) -> usize {
+        self.symbols.get(index).map_or(self.len, |(range, _)| range.start)
+    }
+
+    // Indeks za ostatnim symbolem dopasowania zaczynającego się od `start`.
+    // Wygrywa ostatnia akceptacja: przy `LeftmostFirst` runner zostawia po
+    // akceptacji tylko ścieżki ważniejsze od niej.
+    fn match_at(&mut self, start: usize) -> Option<usize> {
+        self.runner.reset();
+        let mut end = None;
+        for index in start..=self.symbols.len() {
+            if self.runner.is_accepting() {
+                end = Some(index);
+            }
+            if index == self.symbols.len() || !self.runner.feed(iter::once(self.symbols[index].1.clone())) {
+                break;
+            }
+        }
+        end
+    }
+}
+
+impl<S: Symbol, R: Runner<S>> Iterator for Matches<S, R> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        while self.next <= self.symbols.len() {
+            let start = self.next;
+            if let Some(end) = self.match_at(start)
+                && (end > start || self.last_end != Some(start))
+            {
+                // Po pustym dopasowaniu przesuwamy się o jeden symbol
+                self.next = if end > start { end } else { start + 1 };
+                self.last_end = Some(end);
+                let end_offset = if end > start { self.symbols[end - 1].0.end } else { self.offset(start) };
+                return Some(Match {
+                    start: self.offset(start),
+                    end: end_offset,
+                });
+            }
+            self.next += 1;
+        }
+        None
+    }
+}
+
+impl<S: Symbol> DAS<S> {
+    pub fn find<H: Haystack<S> + ?Sized>(&self, haystack: &H) -> Option<Match> {
+        self.find_with(haystack, MatchKind::default())
+    }
+
+    pub fn find_with<H: Haystack<S> + ?Sized>(&self, haystack: &H, kind: MatchKind) -> Option<Match> {
+        self.find_iter_with(haystack, kind).next()
+    }
+
+    pub fn find_iter<H: Haystack<S> + ?Sized>(&self, haystack: &H) -> Matches<S, DasRunner<'_, S>> {
+        self.find_iter_with(haystack, MatchKind::default())
+    }
+
+    // Oba rodzaje dopasowań są dla DAS równoważne (zob. `MatchKind`)
+    pub fn find_iter_with<H: Haystack<S> + ?Sized>(
+        &self,
+        haystack: &H,
+        _kind: MatchKind,
+    ) -> Matches<S, DasRunner<'_, S>> {
+        Matches::new(self.start(), haystack.symbols())
+    }
+
+    // Czy automat akceptuje jakikolwiek fragment tekstu
+    pub fn is_match<H: Haystack<S> + ?Sized>(&self, haystack: &H) -> bool {
+        self.find(haystack).is_some()
+    }
+}
+
+impl<S: Symbol> ENAS<S> {
+    pub fn find<H: Haystack<S> + ?Sized>(&self, haystack: &H) -> Option<Match> {
+        self.find_with(haystack, MatchKind::default())
+    }
+
+    pub fn find_with<H: Haystack<S> + ?Sized>(&self, haystack: &H, kind: MatchKind) -> Option<Match> {
+        self.find_iter_with(haystack, kind).next()
+    }
+
+    pub fn find_iter<H: Haystack<S> + ?Sized>(&self, haystack: &H) -> Matches<S, PriorityRunner<'_, S>> {
+        self.find_iter_with(haystack, MatchKind::default())
+    }
+
+    pub fn find_iter_with<H: Haystack<S> + ?Sized>(
+        &self,
+        haystack: &H,
+        kind: MatchKind,
+    ) -> Matches<S, PriorityRunner<'_, S>> {
+        Matches::new(PriorityRunner::new(self, kind), haystack.symbols())
+    }
+
+    // Czy automat akceptuje jakikolwiek fragment tekstu
+    pub fn is_match<H: Haystack<S> + ?Sized>(&self, haystack: &H) -> bool {
+        self.find(haystack).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::Alphabet::Alphabet;
+    use crate::elements::Node::DASNode;
+
+    fn spans(matches: impl Iterator<Item = Match>) -> Vec<Range<usize>> {
+        matches.map(|m| m.range()).collect()
+    }
+
+    #[test]
+    fn leftmost_longest() {
+        let das = ENAS::from_regex("ab+").unwrap().determinize();
+        assert_eq!(das.find("xxabbbab").map(|m| m.range()), Some(2..6));
+        assert_eq!(spans(das.find_iter("xxabbbab")), [2..6, 6..8]);
+        assert_eq!(spans(das.find_iter_with("xxabbbab", MatchKind::LeftmostFirst)), [2..6, 6..8]);
+        assert!(!das.is_match("bbba"));
+    }
+
+    // Dopasowania jako pary (początek, koniec)
+    fn pairs(matches: impl Iterator<Item = Match>) -> Vec<(usize, usize)> {
+        matches.map(|m| (m.start, m.end)).collect()
+    }
+
+    fn first(pattern: &str, text: &str) -> Vec<(usize, usize)> {
+        pairs(ENAS::from_regex(pattern).unwrap().find_iter_with(text, MatchKind::LeftmostFirst))
+    }
+
+    #[test]
+    fn leftmost_first_prefers_earlier_alternative() {
+        assert_eq!(first("a|ab", "abab"), [(0, 1), (2, 3)]);
+        assert_eq!(first("ab|a", "abab"), [(0, 2), (2, 4)]);
+        assert_eq!(first("sam|samwise", "samwise"), [(0, 3)]);
+        assert_eq!(pairs(ENAS::from_regex("sam|samwise").unwrap().find_iter("samwise")), [(0, 7)]);
+        // Pierwsza alternatywa nie pasuje dalej – wygrywa druga
+        assert_eq!(first("(a|ab)c", "abc"), [(0, 3)]);
+    }
+
+    #[test]
+    fn leftmost_first_repetition_is_greedy() {
+        assert_eq!(first("a*", "baab"), [(0, 0), (1, 3), (4, 4)]);
+        assert_eq!(first("(a|ab)*", "abab"), [(0, 1), (2, 3), (4, 4)]);
+        assert_eq!(first("(ab|a)*", "abab"), [(0, 4)]);
+        assert_eq!(first("a?ab", "ab"), [(0, 2)]);
+    }
+
+    #[test]
+    fn leftmost_first_follows_target_order() {
+        // Z q0 najpierw do q1 (słowo "a"), potem do q2 ("ab")
+        let text = "alphabet a b\nstart q0\naccept q3\nq0 -ε-> q1, q2\nq1 -a-> q3\nq2 -a-> q4\nq4 -b-> q3";
+        let enas = crate::formats::Dsl::parse(text).unwrap().to_enas();
+        assert_eq!(pairs(enas.find_iter_with("ab", MatchKind::LeftmostFirst)), [(0, 1)]);
+        assert_eq!(pairs(enas.find_iter("ab")), [(0, 2)]);
+    }
+
+    #[test]
+    fn empty_matches_advance_by_one_symbol() {
+        let enas = ENAS::from_regex("a*").unwrap();
+        assert_eq!(spans(enas.find_iter("baab")), [0..0, 1..3, 4..4]);
+    }
+
+    #[test]
+    fn spans_are_byte_offsets() {
+        let das = ENAS::from_regex("ż+").unwrap().determinize();
+        assert_eq!(spans(das.find_iter("ażżbż")), [1..5, 6..8]);
+    }
+
+    #[test]
+    fn byte_haystack() {
+        let mut alphabet = Alphabet::new();
+        alphabet.add(b'a');
+        let mut das = DAS::new(alphabet);
+        for (name, accepting) in [("s", false), ("q", true)] {
+            let mut node = DASNode::new(name, accepting);
+            node.add_connection(b'a', "q");
+            das.add_state(node);
+        }
+        das.set_start_state("s");
+        let haystack: &[u8] = b"xaay";
+        assert_eq!(das.find_iter(haystack).collect::<Vec<_>>(), [Match { start: 1, end: 3 }]);
+    }
+
+    #[test]
+    fn token_haystack() {
+        let tokens = Haystack::<Token>::symbols("  let x  =1 ");
+        let ranges: Vec<Range<usize>> = tokens.into_iter().map(|(range, _)| range).collect();
+        assert_eq!(ranges, [2..5, 6..7, 9..11]);
+    }
+}
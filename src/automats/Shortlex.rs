@@ -0,0 +1,122 @@
+use std::collections::VecDeque;
+
+type StepFn<'a, St, S> = Box<dyn Fn(&St, &S) -> Option<St> + 'a>;
+
+// Iterator po słowach języka w porządku shortlex: najpierw krótsze słowa,
+// słowa tej samej długości w kolejności symboli alfabetu. Przeszukiwanie
+// wszerz drzewa słów daje ten porządek bez sortowania.
+//
+// `step` zwraca stan po przeczytaniu symbolu albo None, gdy gałąź można
+// odciąć (żadne jej przedłużenie nie będzie zwrócone).
+pub struct LanguageIter<'a, St, S = char> {
+    queue: VecDeque<(Vec<S>, St)>,
+    symbols: Vec<S>,
+    max_len: usize,
+    step: StepFn<'a, St, S>,
+    accepting: Box<dyn Fn(&St) -> bool + 'a>,
+}
+
+impl<'a, St, S: Clone> LanguageIter<'a, St, S> {
+    pub(crate) fn new(
+        start: Option<St>,
+        symbols: Vec<S>,
+        max_len: usize,
+        step: impl Fn(&St, &S) -> Option<St> + 'a,
+        accepting: impl Fn(&St) -> bool + 'a,
+    ) -> Self {
+        Self {
+            queue: start.map(|s| (Vec::new(), s)).into_iter().collect(),
+            symbols,
+            max_len,
+            step: Box::new(step),
+            accepting: Box::new(accepting),
+        }
+    }
+}
+
+impl<St, S: Clone> Iterator for LanguageIter<'_, St, S> {
+    type Item = Vec<S>;
+
+    fn next(&mut self) -> Option<Vec<S>> {
+        while let Some((word, state)) = self.queue.pop_front() {
+            if word.len() < self.max_len {
+                for symbol in &self.symbols {
+                    if let Some(next) = (self.step)(&state, symbol) {
+                        let mut next_word = word.clone();
+                        next_word.push(symbol.clone());
+                        self.queue.push_back((next_word, next));
+                    }
+                }
+            }
+            if (self.accepting)(&state) {
+                return Some(word);
+            }
+        }
+        None
+    }
+}
+
+// Wszystkie słowa nad `symbols` o długości do `max_len` – do testów
+// porównujących różne implementacje na pełnym zbiorze krótkich słów
+#[cfg(test)]
+pub(crate) fn all_words(symbols: &[char], max_len: usize) -> Vec<String> {
+    LanguageIter::new(Some(()), symbols.to_vec(), max_len, |_, _| Some(()), |_| true)
+        .map(|w| w.into_iter().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::Dsl;
+
+    fn strings(words: impl Iterator<Item = Vec<char>>) -> Vec<String> {
+        words.map(|w| w.into_iter().collect()).collect()
+    }
+
+    fn is_shortlex(words: &[String]) -> bool {
+        words.windows(2).all(|w| (w[0].len(), &w[0]) < (w[1].len(), &w[1]))
+    }
+
+    #[test]
+    fn all_words_in_shortlex_order() {
+        assert_eq!(all_words(&['a', 'b'], 2), ["", "a", "b", "aa", "ab", "ba", "bb"]);
+    }
+
+    #[test]
+    fn max_len_bounds_words() {
+        // Słowa parzystej długości; brak ograniczenia dałby nieskończony ciąg
+        let even = |max_len| strings(LanguageIter::new(Some(0), vec!['a'], max_len, |n, _| Some(n + 1), |n| n % 2 == 0));
+        assert_eq!(even(0), [""]);
+        assert_eq!(even(3), ["", "aa"]);
+        assert_eq!(even(4), ["", "aa", "aaaa"]);
+        assert!(strings(LanguageIter::new(None::<()>, vec!['a'], 5, |_, _| Some(()), |_| true)).is_empty());
+    }
+
+    #[test]
+    fn das_accepted_and_rejected_words() {
+        let das = Dsl::parse("alphabet a b\nstart q0\naccept q1\nq0 -a-> q1\nq0 -b-> q0\nq1 -a,b-> q1")
+            .unwrap()
+            .to_enas()
+            .determinize();
+        let accepted = strings(das.accepted_words(3));
+        let rejected = strings(das.rejected_words(3));
+        assert_eq!(rejected, ["", "b", "bb", "bbb"]);
+        assert_eq!(accepted.len() + rejected.len(), 1 + 2 + 4 + 8);
+        assert!(is_shortlex(&accepted));
+        assert!(accepted.iter().all(|w| w.len() <= 3 && w.contains('a')));
+    }
+
+    #[test]
+    fn enas_words_are_not_repeated() {
+        // "a" i "ab" są akceptowane kilkoma ścieżkami
+        let enas = Dsl::parse("alphabet a b\nstart q0\naccept q2 q3\nq0 -a-> q1, q2\nq0 -ε-> q4\nq4 -a-> q2\n\
+                               q1 -ε-> q2\nq2 -b-> q3\nq1 -b-> q3")
+            .unwrap()
+            .to_enas();
+        assert_eq!(strings(enas.accepted_words(5)), ["a", "ab"]);
+        let rejected = strings(enas.rejected_words(2));
+        assert_eq!(rejected, ["", "b", "aa", "ba", "bb"]);
+        assert!(is_shortlex(&rejected));
+    }
+}
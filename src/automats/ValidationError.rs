@@ -0,0 +1,87 @@
+use crate::elements::Label::Label;
+use crate::elements::Symbol::Symbol;
+use std::fmt;
+
+// Błąd walidacji automatu – wskazuje symbol i (poza błędami alfabetu) stan,
+// których dotyczy, dzięki czemu GUI może go przypisać do konkretnej komórki tabeli
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ValidationError<S = char> {
+    // Przejście prowadzi do stanu, który nie istnieje
+    UnknownTarget {
+        state: String,
+        symbol: S,
+        target: String,
+    },
+    // Stan nie ma przejścia dla znaku alfabetu (tylko DAS)
+    MissingTransition { state: String, symbol: S },
+    // Alfabet zawiera symbol zarezerwowany dla ε
+    ReservedSymbol { symbol: S },
+}
+
+impl<S> ValidationError<S> {
+    pub fn state(&self) -> Option<&str> {
+        match self {
+            ValidationError::UnknownTarget { state, .. } => Some(state),
+            ValidationError::MissingTransition { state, .. } => Some(state),
+            ValidationError::ReservedSymbol { .. } => None,
+        }
+    }
+
+    pub fn symbol(&self) -> &S {
+        match self {
+            ValidationError::UnknownTarget { symbol, .. } => symbol,
+            ValidationError::MissingTransition { symbol, .. } => symbol,
+            ValidationError::ReservedSymbol { symbol } => symbol,
+        }
+    }
+
+    // Zamienia typ symbolu, np. na tekst wyświetlany w GUI
+    pub fn map_symbol<T>(self, f: impl FnOnce(S) -> T) -> ValidationError<T> {
+        match self {
+            ValidationError::UnknownTarget { state, symbol, target } => ValidationError::UnknownTarget {
+                state,
+                symbol: f(symbol),
+                target,
+            },
+            ValidationError::MissingTransition { state, symbol } => ValidationError::MissingTransition {
+                state,
+                symbol: f(symbol),
+            },
+            ValidationError::ReservedSymbol { symbol } => ValidationError::ReservedSymbol { symbol: f(symbol) },
+        }
+    }
+}
+
+impl<S: Symbol> From<ValidationError<S>> for ValidationError<String> {
+    fn from(error: ValidationError<S>) -> Self {
+        error.map_symbol(|s| s.to_string())
+    }
+}
+
+impl<S: Symbol> From<ValidationError<Label<S>>> for ValidationError<String> {
+    fn from(error: ValidationError<Label<S>>) -> Self {
+        error.map_symbol(|label| label.to_string())
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for ValidationError<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::UnknownTarget { state, symbol, target } => write!(
+                f,
+                "Stan '{}' ma połączenie dla '{}' do nieistniejącego stanu '{}'.",
+                state, symbol, target
+            ),
+            ValidationError::MissingTransition { state, symbol } => write!(
+                f,
+                "Stan '{}' nie ma połączenia dla znaku '{}'.",
+                state, symbol
+            ),
+            ValidationError::ReservedSymbol { symbol } => write!(
+                f,
+                "Symbol '{}' jest zarezerwowany dla przejść ε i nie może należeć do alfabetu.",
+                symbol
+            ),
+        }
+    }
+}
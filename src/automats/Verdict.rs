@@ -0,0 +1,53 @@
+use crate::elements::Symbol::Symbol;
+use std::fmt;
+
+// Świadek rozstrzygnięcia własności języka
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Witness<S = char> {
+    Accepted(Vec<S>),
+    Rejected(Vec<S>),
+    // Każde słowo prefix · cycleⁿ · suffix jest akceptowane
+    Cycle {
+        prefix: Vec<S>,
+        cycle: Vec<S>,
+        suffix: Vec<S>,
+    },
+}
+
+// Wynik procedury decyzyjnej: czy własność zachodzi i (jeśli jest) świadek
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verdict<S = char> {
+    pub holds: bool,
+    pub witness: Option<Witness<S>>,
+}
+
+impl<S> Verdict<S> {
+    pub fn new(holds: bool, witness: Option<Witness<S>>) -> Self {
+        Self { holds, witness }
+    }
+}
+
+// Słowo do wyświetlenia; puste słowo to ε
+pub fn format_word<S: Symbol>(word: &[S]) -> String {
+    if word.is_empty() {
+        "ε".to_string()
+    } else {
+        S::format_word(word)
+    }
+}
+
+impl<S: Symbol> fmt::Display for Witness<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Witness::Accepted(w) => write!(f, "akceptowane słowo \"{}\"", format_word(w)),
+            Witness::Rejected(w) => write!(f, "odrzucone słowo \"{}\"", format_word(w)),
+            Witness::Cycle { prefix, cycle, suffix } => write!(
+                f,
+                "pompowalny cykl {}({})ⁿ{}",
+                S::format_word(prefix),
+                S::format_word(cycle),
+                S::format_word(suffix)
+            ),
+        }
+    }
+}
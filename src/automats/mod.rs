@@ -1,2 +1,13 @@
 pub mod DAS;
 pub mod ENAS;
+pub mod ValidationError;
+pub mod Verdict;
+pub mod Shortlex;
+pub mod Counting;
+pub mod CompiledDas;
+pub mod CompiledEnas;
+pub mod Runner;
+pub mod Search;
+pub mod Regex;
+mod Graph;
+mod BitSet;
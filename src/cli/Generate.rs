@@ -0,0 +1,51 @@
+use rust_state_machine::automats::ENAS::ENAS;
+use rust_state_machine::codegen::Backend;
+use rust_state_machine::formats::Automat;
+
+const USAGE: &str = "Użycie: rust-state-machine generate <język> (-r <wyrażenie> | -f <plik>) [-o <plik>]";
+
+// generate <język> (-r <wyrażenie> | -f <plik>) [-o <plik>] – kod automatu dla
+// wyrażenia regularnego albo automatu z pliku; bez -o wynik trafia na stdout
+pub fn run(args: &[String]) -> Result<(), String> {
+    let Some(name) = args.first() else {
+        return Err(USAGE.to_string());
+    };
+    let backend = Backend::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = Backend::ALL.iter().map(|b| b.name()).collect();
+        format!("Nieznany język '{}'. Dostępne: {}.", name, names.join(", "))
+    })?;
+
+    let mut regex = None;
+    let mut file = None;
+    let mut output = None;
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "-r" => regex = rest.next(),
+            "-f" => file = rest.next(),
+            "-o" => output = rest.next(),
+            _ => return Err(format!("Nieoczekiwany argument '{}'.\n{}", arg, USAGE)),
+        }
+    }
+    let das = match (regex, file) {
+        (Some(regex), None) => ENAS::from_regex(regex)?.determinize(),
+        (None, Some(path)) => match super::read_automat(path)? {
+            Automat::DAS(das) => das,
+            Automat::ENAS(enas) => enas.determinize(),
+        },
+        _ => return Err(USAGE.to_string()),
+    };
+
+    // Stany martwe są zbędne – brak przejścia i tak oznacza odrzucenie;
+    // dla pustego języka zostaje sam stan startowy
+    let trimmed = das.trim();
+    let das = if trimmed.get_states().is_empty() { das } else { trimmed };
+    let code = backend.generate(&das.renumbered())?;
+    match output {
+        Some(path) => std::fs::write(path, code).map_err(|e| format!("Nie można zapisać '{}': {}", path, e)),
+        None => {
+            print!("{}", code);
+            Ok(())
+        }
+    }
+}
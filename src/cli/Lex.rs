@@ -0,0 +1,36 @@
+use rust_state_machine::lexer::Lexer::{LexemeKind, LexerBuilder};
+
+use crate::cli::read_input;
+
+// Numer wiersza i kolumny (od 1) dla pozycji bajtowej
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    (line, column)
+}
+
+// lex <plik z regułami> [plik wejściowy] – wypisuje po jednym leksemie w wierszu
+pub fn run(args: &[String]) -> Result<(), String> {
+    let Some(rules_path) = args.first() else {
+        return Err("Użycie: rust-state-machine lex <reguły> [wejście]".to_string());
+    };
+    let rules = std::fs::read_to_string(rules_path)
+        .map_err(|e| format!("Nie można odczytać '{}': {}", rules_path, e))?;
+    let lexer = LexerBuilder::parse(&rules)?.build();
+    let input = read_input(args.get(1))?;
+
+    let mut errors = 0;
+    for lexeme in lexer.tokenize(&input) {
+        let (line, column) = line_column(&input, lexeme.span.start);
+        if lexeme.kind == LexemeKind::Error {
+            errors += 1;
+            eprintln!("{}:{}: nierozpoznany tekst {:?}", line, column, lexeme.text);
+        }
+        println!("{}:{}\t{}", line, column, lexeme);
+    }
+    if errors > 0 {
+        return Err(format!("Liczba błędów leksykalnych: {}.", errors));
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use rust_state_machine::automats::ENAS::ENAS;
+use rust_state_machine::diagram::Layout::LayoutKind;
+use rust_state_machine::diagram::{Png, Svg};
+
+const USAGE: &str =
+    "Użycie: rust-state-machine render (-r <wyrażenie> | -f <plik>) [-o <plik.svg|plik.png>] [-l zapisany|warstwowy|silowy]";
+
+// render – diagram automatu jako SVG albo PNG (według rozszerzenia pliku
+// wyjściowego); bez -o SVG trafia na stdout
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut regex = None;
+    let mut file = None;
+    let mut output = None;
+    let mut layout = LayoutKind::Stored;
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "-r" => regex = rest.next(),
+            "-f" => file = rest.next(),
+            "-o" => output = rest.next(),
+            "-l" => {
+                layout = match rest.next().map(String::as_str) {
+                    Some("zapisany") => LayoutKind::Stored,
+                    Some("warstwowy") => LayoutKind::Layered,
+                    Some("silowy") => LayoutKind::Force,
+                    Some(other) => return Err(format!("Nieznany układ '{}'.\n{}", other, USAGE)),
+                    None => return Err(USAGE.to_string()),
+                }
+            }
+            _ => return Err(format!("Nieoczekiwany argument '{}'.\n{}", arg, USAGE)),
+        }
+    }
+
+    let enas = match (regex, file) {
+        (Some(regex), None) => ENAS::from_regex(regex)?,
+        (None, Some(path)) => super::read_automat(path)?.to_enas(),
+        _ => return Err(USAGE.to_string()),
+    };
+    let svg = Svg::render(&enas, layout);
+    match output {
+        Some(path) if path.to_lowercase().ends_with(".png") => {
+            let png = Png::render(&svg, 2.0)?;
+            std::fs::write(path, png).map_err(|e| format!("Nie można zapisać '{}': {}", path, e))
+        }
+        Some(path) => std::fs::write(path, svg).map_err(|e| format!("Nie można zapisać '{}': {}", path, e)),
+        None => {
+            print!("{}", svg);
+            Ok(())
+        }
+    }
+}
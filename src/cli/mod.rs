@@ -0,0 +1,67 @@
+pub mod Generate;
+pub mod Lex;
+pub mod Render;
+
+use rust_state_machine::formats::Table::TransitionTable;
+use rust_state_machine::formats::{Att, Automat, Dsl, Jflap};
+use std::path::Path;
+
+const USAGE: &str = "Użycie:
+  rust-state-machine                          uruchamia interfejs graficzny
+  rust-state-machine lex <reguły> [wejście]   dzieli wejście (lub stdin) na leksemy
+  rust-state-machine generate <język> (-r <wyrażenie> | -f <plik>) [-o <plik>]
+                                              generuje kod automatu dla wyrażenia albo pliku
+                                              (języki: rust, rust-table, c, c-table, js, ts)
+  rust-state-machine render (-r <wyrażenie> | -f <plik>) [-o <plik.svg|plik.png>] [-l <układ>]
+                                              rysuje diagram automatu
+                                              (układy: zapisany, warstwowy, silowy)
+
+Pliki automatów rozpoznawane są po rozszerzeniu: .jff (JFLAP), .csv, .tsv,
+.fst.txt (AT&T/OpenFst, tabele symboli z .isyms i .ssyms obok), pozostałe
+jako zapis tekstowy.";
+
+// Polecenia wiersza poleceń; bez argumentów uruchamiane jest GUI
+pub fn run(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("lex") => Lex::run(&args[1..]),
+        Some("generate") => Generate::run(&args[1..]),
+        Some("render") => Render::run(&args[1..]),
+        Some("-h" | "--help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => Err(format!("Nieznane polecenie '{}'.\n{}", command, USAGE)),
+        None => Err(USAGE.to_string()),
+    }
+}
+
+// Zawartość pliku albo standardowego wejścia, gdy nie podano ścieżki
+pub fn read_input(path: Option<&String>) -> Result<String, String> {
+    match path {
+        Some(path) => std::fs::read_to_string(path).map_err(|e| format!("Nie można odczytać '{}': {}", path, e)),
+        None => std::io::read_to_string(std::io::stdin()).map_err(|e| format!("Nie można odczytać stdin: {}", e)),
+    }
+}
+
+// Automat z pliku w formacie wskazanym przez rozszerzenie; dla AT&T tabele
+// symboli są brane z plików o tej samej nazwie i rozszerzeniach .isyms, .ssyms
+pub fn read_automat(path: &str) -> Result<Automat, String> {
+    let read = |path: &str| std::fs::read_to_string(path).map_err(|e| format!("Nie można odczytać '{}': {}", path, e));
+    let text = read(path)?;
+    let lower = path.to_lowercase();
+    if lower.ends_with(".jff") {
+        Jflap::read(&text)
+    } else if lower.ends_with(".csv") {
+        TransitionTable::parse(&text, crate::csv_delimiter(&text))?.to_automat()
+    } else if lower.ends_with(".tsv") {
+        TransitionTable::parse(&text, '\t')?.to_automat()
+    } else if let Some(stem) = [".fst.txt", ".fst"].iter().find_map(|e| lower.ends_with(e).then(|| &path[..path.len() - e.len()])) {
+        let table = |extension: &str| {
+            let table = format!("{}{}", stem, extension);
+            Path::new(&table).exists().then(|| read(&table)).transpose()
+        };
+        Att::read(&text, table(".isyms")?.as_deref(), table(".ssyms")?.as_deref())
+    } else {
+        Dsl::parse(&text).map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,235 @@
+use crate::automats::DAS::DAS;
+use crate::codegen::{block_comment, check, grouped_transitions, is_accepting, state_names, Case, StateNames, HEADER};
+use std::fmt::Write;
+
+// Postać wygenerowanej funkcji przejścia
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Style {
+    // Zagnieżdżone instrukcje `switch` po stanie i znaku
+    #[default]
+    Switch,
+    // Statyczne tablice w pamięci tylko do odczytu (dobre dla mikrokontrolerów)
+    Table,
+}
+
+// Dekoder UTF-8 bez alokacji; niepoprawne sekwencje dają U+FFFD, czyli znak
+// spoza alfabetu
+const DECODER: &str = "
+/* Kolejny znak UTF-8 napisu; 0 oznacza koniec */
+static uint32_t automat_decode(const unsigned char **input) {
+    const unsigned char *s = *input;
+    uint32_t c = s[0];
+    int length = c < 0x80 ? 1 : (c >> 5) == 0x6 ? 2 : (c >> 4) == 0xE ? 3 : (c >> 3) == 0x1E ? 4 : 0;
+    if (c == 0) {
+        return 0;
+    }
+    if (length == 0) {
+        *input = s + 1;
+        return 0xFFFD;
+    }
+    if (length > 1) {
+        c &= 0x7F >> length;
+        for (int i = 1; i < length; i++) {
+            if ((s[i] & 0xC0) != 0x80) {
+                *input = s + i;
+                return 0xFFFD;
+            }
+            c = (c << 6) | (s[i] & 0x3F);
+        }
+    }
+    *input = s + length;
+    return c;
+}
+";
+
+const MATCHES: &str = "
+bool automat_matches(const char *input) {
+    const unsigned char *p = (const unsigned char *)input;
+    automat_state state = AUTOMAT_START;
+    uint32_t c;
+    while ((c = automat_decode(&p)) != 0) {
+        state = automat_next(state, c);
+        if (state == AUTOMAT_DEAD) {
+            return false;
+        }
+    }
+    return automat_is_accepting(state);
+}
+";
+
+// Samodzielny plik C99 z typem `automat_state` (stała na stan DAS oraz
+// AUTOMAT_DEAD) i funkcją `bool automat_matches(const char *input)` dla
+// napisów UTF-8 zakończonych zerem
+pub fn generate(das: &DAS, style: Style) -> Result<String, String> {
+    check(das)?;
+    let names = state_names(das, Case::UpperSnake, &["DEAD"]);
+    let mut code = String::new();
+    writeln!(code, "/* {} */", HEADER).unwrap();
+    writeln!(code).unwrap();
+    writeln!(code, "#include <stdbool.h>").unwrap();
+    writeln!(code, "#include <stdint.h>").unwrap();
+    writeln!(code).unwrap();
+    write_enum(&mut code, &names);
+    code.push_str(DECODER);
+    writeln!(code).unwrap();
+    match style {
+        Style::Switch => write_switch(&mut code, das, &names),
+        Style::Table => write_table(&mut code, das, &names),
+    }
+    code.push_str(MATCHES);
+    Ok(code)
+}
+
+fn write_enum(code: &mut String, names: &StateNames) {
+    writeln!(code, "typedef enum {{").unwrap();
+    for (state, identifier) in names.states.iter().zip(&names.identifiers) {
+        writeln!(code, "    AUTOMAT_{}, /* {} */", identifier, block_comment(&format!("{:?}", state))).unwrap();
+    }
+    writeln!(code, "    AUTOMAT_DEAD /* brak przejścia – słowo odrzucone */").unwrap();
+    writeln!(code, "}} automat_state;").unwrap();
+    writeln!(code).unwrap();
+    writeln!(code, "#define AUTOMAT_START AUTOMAT_{}", names.identifiers[0]).unwrap();
+}
+
+fn write_switch(code: &mut String, das: &DAS, names: &StateNames) {
+    writeln!(code, "automat_state automat_next(automat_state state, uint32_t c) {{").unwrap();
+    if names.states.iter().all(|s| das.get_states()[s].get_connections().is_empty()) {
+        writeln!(code, "    (void)state;").unwrap();
+        writeln!(code, "    (void)c;").unwrap();
+        writeln!(code, "    return AUTOMAT_DEAD;").unwrap();
+    } else {
+        writeln!(code, "    switch (state) {{").unwrap();
+        for state in &names.states {
+            let groups = grouped_transitions(das, state);
+            if groups.is_empty() {
+                continue;
+            }
+            writeln!(code, "    case AUTOMAT_{}:", names.identifier(state)).unwrap();
+            writeln!(code, "        switch (c) {{").unwrap();
+            for (target, symbols) in groups {
+                for c in symbols {
+                    writeln!(code, "        case {}:", case_label(c)).unwrap();
+                }
+                writeln!(code, "            return AUTOMAT_{};", names.identifier(&target)).unwrap();
+            }
+            writeln!(code, "        default:").unwrap();
+            writeln!(code, "            return AUTOMAT_DEAD;").unwrap();
+            writeln!(code, "        }}").unwrap();
+        }
+        writeln!(code, "    default:").unwrap();
+        writeln!(code, "        return AUTOMAT_DEAD;").unwrap();
+        writeln!(code, "    }}").unwrap();
+    }
+    writeln!(code, "}}").unwrap();
+    writeln!(code).unwrap();
+
+    writeln!(code, "bool automat_is_accepting(automat_state state) {{").unwrap();
+    let accepting: Vec<&String> = names.states.iter().filter(|s| is_accepting(das, s)).collect();
+    if accepting.is_empty() {
+        writeln!(code, "    (void)state;").unwrap();
+        writeln!(code, "    return false;").unwrap();
+    } else {
+        writeln!(code, "    switch (state) {{").unwrap();
+        for state in accepting {
+            writeln!(code, "    case AUTOMAT_{}:", names.identifier(state)).unwrap();
+        }
+        writeln!(code, "        return true;").unwrap();
+        writeln!(code, "    default:").unwrap();
+        writeln!(code, "        return false;").unwrap();
+        writeln!(code, "    }}").unwrap();
+    }
+    writeln!(code, "}}").unwrap();
+}
+
+fn write_table(code: &mut String, das: &DAS, names: &StateNames) {
+    let alphabet = das.alphabet().symbols();
+    let (n, k) = (names.states.len(), alphabet.len());
+    // C nie dopuszcza tablic o rozmiarze 0 – pusty alfabet dostaje atrapę
+    let width = k.max(1);
+    let cell = match n + 1 {
+        0..=256 => "uint8_t",
+        257..=65536 => "uint16_t",
+        _ => "uint32_t",
+    };
+
+    writeln!(code, "/* Kolumny tablicy przejść – posortowane, szukane binarnie */").unwrap();
+    writeln!(code, "#define AUTOMAT_SYMBOL_COUNT {}", k).unwrap();
+    let symbols: Vec<String> = if k == 0 {
+        vec!["0".to_string()]
+    } else {
+        alphabet.iter().map(|&c| c_char(c)).collect()
+    };
+    writeln!(code, "static const uint32_t automat_alphabet[{}] = {{{}}};", width, symbols.join(", ")).unwrap();
+    let accepting: Vec<String> = names.states.iter().map(|s| is_accepting(das, s).to_string()).collect();
+    writeln!(code, "static const bool automat_accepting[{}] = {{{}}};", n, accepting.join(", ")).unwrap();
+    writeln!(code, "static const {} automat_transitions[{}][{}] = {{", cell, n, width).unwrap();
+    for state in &names.states {
+        let connections = das.get_states()[state].get_connections();
+        let mut row: Vec<String> = alphabet
+            .iter()
+            .map(|c| match connections.get(c) {
+                Some(target) => format!("AUTOMAT_{}", names.identifier(target)),
+                None => "AUTOMAT_DEAD".to_string(),
+            })
+            .collect();
+        if row.is_empty() {
+            row.push("AUTOMAT_DEAD".to_string());
+        }
+        writeln!(code, "    /* AUTOMAT_{} */ {{{}}},", names.identifier(state), row.join(", ")).unwrap();
+    }
+    writeln!(code, "}};").unwrap();
+    code.push_str(
+        "
+static int automat_column(uint32_t c) {
+    int low = 0;
+    int high = AUTOMAT_SYMBOL_COUNT - 1;
+    while (low <= high) {
+        int middle = low + (high - low) / 2;
+        if (automat_alphabet[middle] == c) {
+            return middle;
+        }
+        if (automat_alphabet[middle] < c) {
+            low = middle + 1;
+        } else {
+            high = middle - 1;
+        }
+    }
+    return -1;
+}
+
+automat_state automat_next(automat_state state, uint32_t c) {
+    int column = automat_column(c);
+    if (state == AUTOMAT_DEAD || column < 0) {
+        return AUTOMAT_DEAD;
+    }
+    return (automat_state)automat_transitions[state][column];
+}
+
+bool automat_is_accepting(automat_state state) {
+    return state != AUTOMAT_DEAD && automat_accepting[state];
+}
+",
+    );
+}
+
+// Etykieta `case`; znaki spoza ASCII jako kod z komentarzem
+fn case_label(c: char) -> String {
+    if c.is_ascii() || c.is_control() {
+        c_char(c)
+    } else {
+        format!("{} /* {} */", c_char(c), c)
+    }
+}
+
+// Literał znakowy C dla drukowalnego ASCII, w pozostałych przypadkach kod znaku
+fn c_char(c: char) -> String {
+    match c {
+        '\'' => "'\\''".to_string(),
+        '\\' => "'\\\\'".to_string(),
+        '\n' => "'\\n'".to_string(),
+        '\t' => "'\\t'".to_string(),
+        '\r' => "'\\r'".to_string(),
+        ' '..='~' => format!("'{}'", c),
+        _ => format!("0x{:X}", c as u32),
+    }
+}
@@ -0,0 +1,115 @@
+use crate::automats::DAS::DAS;
+use crate::codegen::{block_comment, check, grouped_transitions, is_accepting, state_names, Case, HEADER};
+use std::fmt::Write;
+
+// Wariant języka; TypeScript dostaje `enum` i adnotacje typów
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dialect {
+    #[default]
+    JavaScript,
+    TypeScript,
+}
+
+// Moduł ES z wyliczeniem `State`, stałą `START` oraz funkcjami `next`,
+// `isAccepting` i `matches(input)`; napis jest czytany po punktach kodowych,
+// tak jak `chars()` w Ruście
+pub fn generate(das: &DAS, dialect: Dialect) -> Result<String, String> {
+    check(das)?;
+    let names = state_names(das, Case::Camel, &[]);
+    let typescript = dialect == Dialect::TypeScript;
+    // Adnotacja typu dodawana tylko w TypeScripcie
+    let annotate = |annotation: &str| if typescript { annotation.to_string() } else { String::new() };
+
+    let mut code = String::new();
+    writeln!(code, "// {}", HEADER).unwrap();
+    writeln!(code).unwrap();
+    if typescript {
+        writeln!(code, "export enum State {{").unwrap();
+    } else {
+        writeln!(code, "export const State = Object.freeze({{").unwrap();
+    }
+    for (i, (state, identifier)) in names.states.iter().zip(&names.identifiers).enumerate() {
+        writeln!(code, "    /** {} */", block_comment(&format!("{:?}", state))).unwrap();
+        if typescript {
+            writeln!(code, "    {} = {},", identifier, i).unwrap();
+        } else {
+            writeln!(code, "    {}: {},", identifier, i).unwrap();
+        }
+    }
+    writeln!(code, "{}", if typescript { "}" } else { "});" }).unwrap();
+    writeln!(code).unwrap();
+    writeln!(code, "export const START{} = State.{};", annotate(": State"), names.identifiers[0]).unwrap();
+    writeln!(code).unwrap();
+
+    writeln!(code, "export function next(state{}, c{}){} {{", annotate(": State"), annotate(": string"), annotate(": State | null")).unwrap();
+    let mut cases = String::new();
+    for state in &names.states {
+        let groups = grouped_transitions(das, state);
+        if groups.is_empty() {
+            continue;
+        }
+        writeln!(cases, "        case State.{}:", names.identifier(state)).unwrap();
+        writeln!(cases, "            switch (c) {{").unwrap();
+        for (target, symbols) in groups {
+            for c in symbols {
+                writeln!(cases, "                case {}:", js_string(c)).unwrap();
+            }
+            writeln!(cases, "                    return State.{};", names.identifier(&target)).unwrap();
+        }
+        writeln!(cases, "            }}").unwrap();
+        writeln!(cases, "            return null;").unwrap();
+    }
+    if !cases.is_empty() {
+        writeln!(code, "    switch (state) {{").unwrap();
+        code.push_str(&cases);
+        writeln!(code, "    }}").unwrap();
+    }
+    writeln!(code, "    return null;").unwrap();
+    writeln!(code, "}}").unwrap();
+    writeln!(code).unwrap();
+
+    writeln!(code, "export function isAccepting(state{}){} {{", annotate(": State"), annotate(": boolean")).unwrap();
+    let accepting: Vec<&String> = names.states.iter().filter(|s| is_accepting(das, s)).collect();
+    if !accepting.is_empty() {
+        writeln!(code, "    switch (state) {{").unwrap();
+        for state in accepting {
+            writeln!(code, "        case State.{}:", names.identifier(state)).unwrap();
+        }
+        writeln!(code, "            return true;").unwrap();
+        writeln!(code, "    }}").unwrap();
+    }
+    writeln!(code, "    return false;").unwrap();
+    writeln!(code, "}}").unwrap();
+    writeln!(code).unwrap();
+
+    write!(
+        code,
+        "export function matches(input{}){} {{
+    let state{} = START;
+    for (const c of input) {{
+        const following = next(state, c);
+        if (following === null) {{
+            return false;
+        }}
+        state = following;
+    }}
+    return isAccepting(state);
+}}
+",
+        annotate(": string"),
+        annotate(": boolean"),
+        annotate(": State")
+    )
+    .unwrap();
+    Ok(code)
+}
+
+// Literał napisowy JavaScriptu; znaki sterujące i separatory wierszy jako \uXXXX
+fn js_string(c: char) -> String {
+    match c {
+        '"' => "\"\\\"\"".to_string(),
+        '\\' => "\"\\\\\"".to_string(),
+        c if c.is_control() || c == '\u{2028}' || c == '\u{2029}' => format!("\"\\u{{{:X}}}\"", c as u32),
+        c => format!("\"{}\"", c),
+    }
+}
@@ -0,0 +1,147 @@
+use crate::automats::DAS::DAS;
+use crate::codegen::{check, grouped_transitions, is_accepting, state_names, Case, StateNames, HEADER};
+use std::fmt::Write;
+
+// Postać wygenerowanej funkcji przejścia
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Style {
+    // Jedno wyrażenie `match` po parach (stan, znak)
+    #[default]
+    Match,
+    // Statyczna tablica przejść indeksowana stanem i kolumną znaku
+    Table,
+}
+
+// Słowa kluczowe, które mogą powstać z nazwy stanu – wszystkie poza `Self`
+// zaczynają się małą literą, a warianty zawsze wielką
+const KEYWORDS: [&str; 1] = ["Self"];
+
+// Samodzielny moduł Rusta z typem `State` (warianty nazwane od stanów DAS)
+// i funkcją `matches(input: &str) -> bool`
+pub fn generate(das: &DAS, style: Style) -> Result<String, String> {
+    check(das)?;
+    let names = state_names(das, Case::Camel, &KEYWORDS);
+    let mut code = String::new();
+    writeln!(code, "// {}", HEADER).unwrap();
+    writeln!(code).unwrap();
+    write_enum(&mut code, &names);
+    match style {
+        Style::Match => write_match(&mut code, das, &names),
+        Style::Table => write_table(&mut code, das, &names),
+    }
+    code.push_str(
+        "
+pub fn matches(input: &str) -> bool {
+    let mut state = State::START;
+    for c in input.chars() {
+        match state.next(c) {
+            Some(next) => state = next,
+            None => return false,
+        }
+    }
+    state.is_accepting()
+}
+",
+    );
+    Ok(code)
+}
+
+fn write_enum(code: &mut String, names: &StateNames) {
+    writeln!(code, "#[derive(Clone, Copy, Debug, PartialEq, Eq)]").unwrap();
+    writeln!(code, "pub enum State {{").unwrap();
+    for (state, identifier) in names.states.iter().zip(&names.identifiers) {
+        writeln!(code, "    /// {:?}", state).unwrap();
+        writeln!(code, "    {},", identifier).unwrap();
+    }
+    writeln!(code, "}}").unwrap();
+    writeln!(code).unwrap();
+}
+
+fn write_match(code: &mut String, das: &DAS, names: &StateNames) {
+    writeln!(code, "impl State {{").unwrap();
+    writeln!(code, "    pub const START: State = State::{};", names.identifiers[0]).unwrap();
+    writeln!(code).unwrap();
+    writeln!(code, "    pub fn next(self, c: char) -> Option<State> {{").unwrap();
+    let mut arms = Vec::new();
+    for state in &names.states {
+        for (target, symbols) in grouped_transitions(das, state) {
+            let pattern = symbols.iter().map(|c| format!("{:?}", c)).collect::<Vec<_>>().join(" | ");
+            arms.push(format!(
+                "            (State::{}, {}) => Some(State::{}),",
+                names.identifier(state),
+                pattern,
+                names.identifier(&target)
+            ));
+        }
+    }
+    if arms.is_empty() {
+        writeln!(code, "        let _ = (self, c);").unwrap();
+        writeln!(code, "        None").unwrap();
+    } else {
+        writeln!(code, "        match (self, c) {{").unwrap();
+        for arm in arms {
+            writeln!(code, "{}", arm).unwrap();
+        }
+        writeln!(code, "            _ => None,").unwrap();
+        writeln!(code, "        }}").unwrap();
+    }
+    writeln!(code, "    }}").unwrap();
+    writeln!(code).unwrap();
+
+    let accepting: Vec<String> = names
+        .states
+        .iter()
+        .filter(|s| is_accepting(das, s))
+        .map(|s| format!("State::{}", names.identifier(s)))
+        .collect();
+    writeln!(code, "    pub fn is_accepting(self) -> bool {{").unwrap();
+    if accepting.is_empty() {
+        writeln!(code, "        false").unwrap();
+    } else {
+        writeln!(code, "        matches!(self, {})", accepting.join(" | ")).unwrap();
+    }
+    writeln!(code, "    }}").unwrap();
+    writeln!(code, "}}").unwrap();
+}
+
+fn write_table(code: &mut String, das: &DAS, names: &StateNames) {
+    let alphabet = das.alphabet().symbols();
+    let (n, k) = (names.states.len(), alphabet.len());
+    writeln!(code, "// Kolumny tablicy przejść – posortowane, szukane binarnie").unwrap();
+    let symbols: Vec<String> = alphabet.iter().map(|c| format!("{:?}", c)).collect();
+    writeln!(code, "const ALPHABET: [char; {}] = [{}];", k, symbols.join(", ")).unwrap();
+    let accepting: Vec<String> = names.states.iter().map(|s| is_accepting(das, s).to_string()).collect();
+    writeln!(code, "const ACCEPTING: [bool; {}] = [{}];", n, accepting.join(", ")).unwrap();
+    writeln!(code, "const TRANSITIONS: [[Option<State>; {}]; {}] = [", k, n).unwrap();
+    for state in &names.states {
+        let connections = das.get_states()[state].get_connections();
+        let row: Vec<String> = alphabet
+            .iter()
+            .map(|c| match connections.get(c) {
+                Some(target) => format!("Some(State::{})", names.identifier(target)),
+                None => "None".to_string(),
+            })
+            .collect();
+        writeln!(code, "    // {}", names.identifier(state)).unwrap();
+        writeln!(code, "    [{}],", row.join(", ")).unwrap();
+    }
+    writeln!(code, "];").unwrap();
+    writeln!(code).unwrap();
+
+    code.push_str(&format!(
+        "impl State {{
+    pub const START: State = State::{};
+
+    pub fn next(self, c: char) -> Option<State> {{
+        let column = ALPHABET.binary_search(&c).ok()?;
+        TRANSITIONS[self as usize][column]
+    }}
+
+    pub fn is_accepting(self) -> bool {{
+        ACCEPTING[self as usize]
+    }}
+}}
+",
+        names.identifiers[0]
+    ));
+}
@@ -0,0 +1,229 @@
+pub mod C;
+pub mod JavaScript;
+pub mod Rust;
+
+use crate::automats::DAS::DAS;
+use crate::elements::Node::NodeBase;
+use std::collections::{BTreeMap, HashSet};
+
+// Generatory wybierane z menu eksportu w GUI i w poleceniu `generate`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    RustMatch,
+    RustTable,
+    CSwitch,
+    CTable,
+    JavaScript,
+    TypeScript,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 6] = [
+        Backend::RustMatch,
+        Backend::RustTable,
+        Backend::CSwitch,
+        Backend::CTable,
+        Backend::JavaScript,
+        Backend::TypeScript,
+    ];
+
+    // Nazwa w wierszu poleceń
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::RustMatch => "rust",
+            Backend::RustTable => "rust-table",
+            Backend::CSwitch => "c",
+            Backend::CTable => "c-table",
+            Backend::JavaScript => "js",
+            Backend::TypeScript => "ts",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Backend> {
+        Backend::ALL.into_iter().find(|b| b.name() == name)
+    }
+
+    // Opis w menu eksportu
+    pub fn label(self) -> &'static str {
+        match self {
+            Backend::RustMatch => "Rust (match)",
+            Backend::RustTable => "Rust (tablica)",
+            Backend::CSwitch => "C (switch)",
+            Backend::CTable => "C (tablica)",
+            Backend::JavaScript => "JavaScript",
+            Backend::TypeScript => "TypeScript",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Backend::RustMatch | Backend::RustTable => "rs",
+            Backend::CSwitch | Backend::CTable => "c",
+            Backend::JavaScript => "js",
+            Backend::TypeScript => "ts",
+        }
+    }
+
+    pub fn generate(self, das: &DAS) -> Result<String, String> {
+        match self {
+            Backend::RustMatch => Rust::generate(das, Rust::Style::Match),
+            Backend::RustTable => Rust::generate(das, Rust::Style::Table),
+            Backend::CSwitch => C::generate(das, C::Style::Switch),
+            Backend::CTable => C::generate(das, C::Style::Table),
+            Backend::JavaScript => JavaScript::generate(das, JavaScript::Dialect::JavaScript),
+            Backend::TypeScript => JavaScript::generate(das, JavaScript::Dialect::TypeScript),
+        }
+    }
+}
+
+// Nagłówek dodawany na początku wygenerowanych plików
+pub(crate) const HEADER: &str = "Wygenerowano przez rust-state-machine – nie edytować ręcznie.";
+
+// Stany w kolejności generowania (startowy pierwszy, reszta alfabetycznie)
+// wraz z identyfikatorami w konwencji języka docelowego
+pub(crate) struct StateNames {
+    pub(crate) states: Vec<String>,
+    pub(crate) identifiers: Vec<String>,
+}
+
+impl StateNames {
+    pub(crate) fn index(&self, state: &str) -> usize {
+        self.states.iter().position(|s| s == state).unwrap()
+    }
+
+    pub(crate) fn identifier(&self, state: &str) -> &str {
+        &self.identifiers[self.index(state)]
+    }
+}
+
+// Generator wymaga automatu bez przejść do nieistniejących stanów i z
+// istniejącym stanem startowym; brakujące przejścia oznaczają odrzucenie
+pub(crate) fn check(das: &DAS) -> Result<(), String> {
+    if let Some(error) = das.validate_with(true).first() {
+        return Err(error.to_string());
+    }
+    if !das.get_states().contains_key(das.get_start_state()) {
+        return Err(format!("Stan startowy '{}' nie istnieje.", das.get_start_state()));
+    }
+    Ok(())
+}
+
+// Konwencja nazewnicza identyfikatorów stanów
+#[derive(Clone, Copy)]
+pub(crate) enum Case {
+    Camel,
+    UpperSnake,
+}
+
+// `reserved` to identyfikatory zajęte przez sam generator (np. stan martwy)
+pub(crate) fn state_names(das: &DAS, case: Case, reserved: &[&str]) -> StateNames {
+    let mut states: Vec<String> = das.get_states().keys().cloned().collect();
+    states.sort();
+    let start = states.iter().position(|s| s == das.get_start_state()).unwrap();
+    let start = states.remove(start);
+    states.insert(0, start);
+
+    let mut used: HashSet<String> = reserved.iter().map(|s| s.to_string()).collect();
+    let identifiers = states
+        .iter()
+        .map(|state| {
+            let (base, separator) = match case {
+                Case::Camel => (camel_case(state), ""),
+                Case::UpperSnake => (upper_snake_case(state), "_"),
+            };
+            let mut identifier = base.clone();
+            let mut suffix = 2;
+            while !used.insert(identifier.clone()) {
+                identifier = format!("{}{}{}", base, separator, suffix);
+                suffix += 1;
+            }
+            identifier
+        })
+        .collect();
+    StateNames { states, identifiers }
+}
+
+// Nazwa stanu jako identyfikator: "q0" → "Q0", "stan-pułapka" → "StanPulapka",
+// "{q0,q1}" → "Q0Q1"; nazwy zaczynające się od cyfry dostają prefiks "S"
+fn camel_case(name: &str) -> String {
+    let mut identifier = String::new();
+    let mut upper = true;
+    for c in name.chars().map(ascii_fold) {
+        if c.is_ascii_alphanumeric() {
+            if upper {
+                identifier.push(c.to_ascii_uppercase());
+            } else {
+                identifier.push(c);
+            }
+            upper = false;
+        } else {
+            upper = true;
+        }
+    }
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, 'S');
+    }
+    identifier
+}
+
+// Nazwa stanu jako stała: "q0" → "Q0", "stan-pułapka" → "STAN_PULAPKA",
+// "{q0,q1}" → "Q0_Q1"; generatory dodają własny prefiks, więc cyfra na
+// początku nie przeszkadza
+fn upper_snake_case(name: &str) -> String {
+    let words: Vec<String> = name
+        .chars()
+        .map(ascii_fold)
+        .collect::<String>()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_ascii_uppercase())
+        .collect();
+    if words.is_empty() {
+        return "S".to_string();
+    }
+    words.join("_")
+}
+
+// Polskie litery zamieniane na odpowiedniki ASCII
+fn ascii_fold(c: char) -> char {
+    match c {
+        'ą' => 'a',
+        'ć' => 'c',
+        'ę' => 'e',
+        'ł' => 'l',
+        'ń' => 'n',
+        'ó' => 'o',
+        'ś' => 's',
+        'ź' | 'ż' => 'z',
+        'Ą' => 'A',
+        'Ć' => 'C',
+        'Ę' => 'E',
+        'Ł' => 'L',
+        'Ń' => 'N',
+        'Ó' => 'O',
+        'Ś' => 'S',
+        'Ź' | 'Ż' => 'Z',
+        c => c,
+    }
+}
+
+// Przejścia stanu pogrupowane według stanu docelowego (znaki posortowane)
+pub(crate) fn grouped_transitions(das: &DAS, state: &str) -> BTreeMap<String, Vec<char>> {
+    let mut groups: BTreeMap<String, Vec<char>> = BTreeMap::new();
+    for (symbol, target) in das.get_states()[state].get_connections() {
+        groups.entry(target.clone()).or_default().push(*symbol);
+    }
+    for symbols in groups.values_mut() {
+        symbols.sort();
+    }
+    groups
+}
+
+// Tekst bezpieczny wewnątrz komentarza /* ... */
+pub(crate) fn block_comment(text: &str) -> String {
+    text.replace("*/", "*\\/")
+}
+
+pub(crate) fn is_accepting(das: &DAS, state: &str) -> bool {
+    das.get_states()[state].is_accepting()
+}
@@ -0,0 +1,256 @@
+use crate::automats::ENAS::ENAS;
+use crate::elements::Node::NodeBase;
+use crate::formats::edges;
+use std::collections::VecDeque;
+
+// Odstępy między stanami w pikselach (jak współrzędne w JFLAP)
+const LAYER_SPACING: f32 = 160.0;
+const ROW_SPACING: f32 = 110.0;
+const MARGIN: f32 = 80.0;
+
+// Sposób rozmieszczenia stanów na diagramie
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LayoutKind {
+    // Położenia zapisane w stanach (np. z pliku JFLAP); brakujące dostają
+    // układ warstwowy pod zapisanymi
+    #[default]
+    Stored,
+    // Warstwy według odległości od stanu startowego, od lewej do prawej;
+    // kolejność w warstwie poprawiana metodą barycentrów
+    Layered,
+    // Symulacja sił (Fruchterman–Reingold) zaczynająca od układu warstwowego
+    Force,
+}
+
+// Położenia stanów w kolejności `order` (oś y w dół)
+pub fn positions(enas: &ENAS, order: &[String], kind: LayoutKind) -> Vec<(f32, f32)> {
+    let neighbours = neighbours(enas, order);
+    match kind {
+        LayoutKind::Layered => layered(&neighbours),
+        LayoutKind::Force => force(&neighbours),
+        LayoutKind::Stored => {
+            let stored: Vec<Option<(f32, f32)>> = order.iter().map(|s| enas.get_states()[s].get_position()).collect();
+            if stored.iter().all(Option::is_none) {
+                return layered(&neighbours);
+            }
+            // Nowe stany (np. dodane po imporcie) trafiają pod istniejący diagram
+            let bottom = stored.iter().flatten().map(|p| p.1).fold(f32::MIN, f32::max);
+            let auto = layered(&neighbours);
+            let top = stored
+                .iter()
+                .zip(&auto)
+                .filter(|(s, _)| s.is_none())
+                .map(|(_, p)| p.1)
+                .fold(f32::MAX, f32::min);
+            stored
+                .iter()
+                .zip(auto)
+                .map(|(s, (x, y))| s.unwrap_or((x, y - top + bottom + ROW_SPACING)))
+                .collect()
+        }
+    }
+}
+
+// Sąsiedzi każdego stanu (bez kierunku): następniki przed poprzednikami
+fn neighbours(enas: &ENAS, order: &[String]) -> Vec<Vec<usize>> {
+    let index = |name: &str| order.iter().position(|s| s == name).unwrap();
+    let mut successors = vec![Vec::new(); order.len()];
+    let mut predecessors = vec![Vec::new(); order.len()];
+    for edge in edges(enas, order) {
+        let (from, to) = (index(&edge.from), index(&edge.to));
+        if from != to {
+            successors[from].push(to);
+            predecessors[to].push(from);
+        }
+    }
+    successors
+        .into_iter()
+        .zip(predecessors)
+        .map(|(mut s, p)| {
+            s.extend(p);
+            s
+        })
+        .collect()
+}
+
+fn layered(neighbours: &[Vec<usize>]) -> Vec<(f32, f32)> {
+    let n = neighbours.len();
+    // Warstwa = odległość BFS od stanu startowego; kolejne niespójne części
+    // grafu zaczynają się za ostatnią warstwą
+    let mut layer = vec![usize::MAX; n];
+    let mut layers: Vec<Vec<usize>> = Vec::new();
+    for root in 0..n {
+        if layer[root] != usize::MAX {
+            continue;
+        }
+        let base = layers.len();
+        layer[root] = base;
+        let mut queue = VecDeque::from([root]);
+        while let Some(v) = queue.pop_front() {
+            if layers.len() <= layer[v] {
+                layers.push(Vec::new());
+            }
+            layers[layer[v]].push(v);
+            for &w in &neighbours[v] {
+                if layer[w] == usize::MAX {
+                    layer[w] = layer[v] + 1;
+                    queue.push_back(w);
+                }
+            }
+        }
+    }
+
+    // Metoda barycentrów: kilka przejść w przód i w tył zmniejsza liczbę przecięć
+    let mut rank = vec![0.0; n];
+    for nodes in &layers {
+        for (i, &v) in nodes.iter().enumerate() {
+            rank[v] = i as f32;
+        }
+    }
+    for sweep in 0..8 {
+        let range: Vec<usize> = if sweep % 2 == 0 { (1..layers.len()).collect() } else { (0..layers.len().saturating_sub(1)).rev().collect() };
+        for l in range {
+            let adjacent = if sweep % 2 == 0 { l - 1 } else { l + 1 };
+            let barycenter = |v: usize| {
+                let ranks: Vec<f32> = neighbours[v].iter().filter(|&&w| layer[w] == adjacent).map(|&w| rank[w]).collect();
+                if ranks.is_empty() { rank[v] } else { ranks.iter().sum::<f32>() / ranks.len() as f32 }
+            };
+            let mut keyed: Vec<(f32, usize)> = layers[l].iter().map(|&v| (barycenter(v), v)).collect();
+            keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+            layers[l] = keyed.into_iter().map(|(_, v)| v).collect();
+            for (i, &v) in layers[l].iter().enumerate() {
+                rank[v] = i as f32;
+            }
+        }
+    }
+
+    let height = layers.iter().map(Vec::len).max().unwrap_or(0) as f32;
+    let mut positions = vec![(0.0, 0.0); n];
+    for (l, nodes) in layers.iter().enumerate() {
+        let offset = (height - nodes.len() as f32) / 2.0;
+        for (i, &v) in nodes.iter().enumerate() {
+            positions[v] = (MARGIN + l as f32 * LAYER_SPACING, MARGIN + (offset + i as f32) * ROW_SPACING);
+        }
+    }
+    positions
+}
+
+fn force(neighbours: &[Vec<usize>]) -> Vec<(f32, f32)> {
+    let n = neighbours.len();
+    let mut positions = layered(neighbours);
+    let k = ROW_SPACING;
+    let iterations = 300;
+    for step in 0..iterations {
+        // Temperatura ogranicza przesunięcie i maleje liniowo do zera
+        let temperature = k * (1.0 - step as f32 / iterations as f32);
+        let mut shift = vec![(0.0f32, 0.0f32); n];
+        for v in 0..n {
+            for w in 0..n {
+                if v == w {
+                    continue;
+                }
+                let (dx, dy) = (positions[v].0 - positions[w].0, positions[v].1 - positions[w].1);
+                let distance = (dx * dx + dy * dy).sqrt().max(1.0);
+                let repulsion = k * k / distance;
+                shift[v].0 += dx / distance * repulsion;
+                shift[v].1 += dy / distance * repulsion;
+            }
+            for &w in &neighbours[v] {
+                let (dx, dy) = (positions[v].0 - positions[w].0, positions[v].1 - positions[w].1);
+                let distance = (dx * dx + dy * dy).sqrt().max(1.0);
+                let attraction = distance * distance / k;
+                shift[v].0 -= dx / distance * attraction;
+                shift[v].1 -= dy / distance * attraction;
+            }
+        }
+        for (p, (dx, dy)) in positions.iter_mut().zip(shift) {
+            let length = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+            let limited = length.min(temperature);
+            p.0 += dx / length * limited;
+            p.1 += dy / length * limited;
+        }
+    }
+
+    // Przesunięcie do dodatnich współrzędnych z marginesem
+    let min_x = positions.iter().map(|p| p.0).fold(f32::MAX, f32::min);
+    let min_y = positions.iter().map(|p| p.1).fold(f32::MAX, f32::min);
+    positions.iter().map(|&(x, y)| (x - min_x + MARGIN, y - min_y + MARGIN)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::{ordered_states, Dsl};
+
+    const KINDS: [LayoutKind; 3] = [LayoutKind::Stored, LayoutKind::Layered, LayoutKind::Force];
+    // Średnica stanu na diagramie SVG
+    const DIAMETER: f32 = 44.0;
+
+    fn layout(enas: &ENAS, kind: LayoutKind) -> Vec<(f32, f32)> {
+        let order = ordered_states(enas.get_start_state(), enas.get_states().keys());
+        let positions = positions(enas, &order, kind);
+        assert_eq!(positions.len(), order.len());
+        positions
+    }
+
+    fn assert_no_overlap(positions: &[(f32, f32)]) {
+        for (i, a) in positions.iter().enumerate() {
+            assert!(a.0.is_finite() && a.1.is_finite());
+            for b in &positions[i + 1..] {
+                let distance = ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt();
+                assert!(distance >= DIAMETER, "{:?} i {:?} nachodzą na siebie", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn states_do_not_overlap() {
+        let enas = Dsl::parse("start q0\naccept q4\nq0 -a-> q1, q2, q3\nq1 -b-> q4\nq2 -a-> q4\nq3 -ε-> q4\nq5 -a-> q6")
+            .unwrap()
+            .to_enas();
+        for kind in KINDS {
+            assert_no_overlap(&layout(&enas, kind));
+        }
+    }
+
+    #[test]
+    fn layered_follows_distance_from_start() {
+        let enas = Dsl::parse("start q0\naccept q2\nq0 -a-> q1\nq1 -a-> q2").unwrap().to_enas();
+        let positions = layout(&enas, LayoutKind::Layered);
+        assert!(positions[0].0 < positions[1].0 && positions[1].0 < positions[2].0);
+    }
+
+    #[test]
+    fn without_start_state() {
+        let mut enas = Dsl::parse("start q0\naccept q2\nq0 -a-> q1\nq1 -b-> q2\nq2 -a-> q0").unwrap().to_enas();
+        enas.set_start_state("brak");
+        for kind in KINDS {
+            assert_no_overlap(&layout(&enas, kind));
+        }
+    }
+
+    #[test]
+    fn self_loops_are_ignored() {
+        let enas = Dsl::parse("start q0\naccept q1\nq0 -a-> q0, q1\nq1 -a,b-> q1").unwrap().to_enas();
+        let single = Dsl::parse("start q0\naccept q1\nq0 -a-> q1").unwrap().to_enas();
+        for kind in KINDS {
+            let positions = layout(&enas, kind);
+            assert_no_overlap(&positions);
+            assert_eq!(positions, layout(&single, kind));
+        }
+    }
+
+    #[test]
+    fn new_states_go_below_stored_ones() {
+        let mut enas = Dsl::parse("start q0\naccept q1\nq0 -a-> q1\nq1 -b-> q2").unwrap().to_enas();
+        for (name, position) in [("q0", (300.0, 40.0)), ("q1", (420.0, 200.0))] {
+            let mut node = enas.get_states()[name].clone();
+            node.set_position(Some(position));
+            enas.add_state(node);
+        }
+        let positions = layout(&enas, LayoutKind::Stored);
+        assert_eq!(positions[..2], [(300.0, 40.0), (420.0, 200.0)]);
+        assert!(positions[2].1 > 200.0);
+        assert_no_overlap(&positions);
+    }
+}
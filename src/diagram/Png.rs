@@ -0,0 +1,14 @@
+use resvg::{tiny_skia, usvg};
+
+// Rasteryzacja dokumentu SVG (np. z `Svg::render`) do PNG; `scale` to
+// powiększenie względem rozmiaru w pikselach zapisanego w dokumencie
+pub fn render(svg: &str, scale: f32) -> Result<Vec<u8>, String> {
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+    let tree = usvg::Tree::from_str(svg, &options).map_err(|e| format!("Niepoprawny SVG: {}", e))?;
+    let size = tree.size().to_int_size().scale_by(scale).ok_or("Niepoprawna skala obrazu.")?;
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height()).ok_or("Obraz jest zbyt duży.")?;
+    pixmap.fill(tiny_skia::Color::WHITE);
+    resvg::render(&tree, tiny_skia::Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+    pixmap.encode_png().map_err(|e| format!("Nie można zakodować PNG: {}", e))
+}
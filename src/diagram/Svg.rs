@@ -0,0 +1,170 @@
+use crate::automats::ENAS::ENAS;
+use crate::diagram::Layout::{self, LayoutKind};
+use crate::elements::Node::NodeBase;
+use crate::formats::{diagram_label, edges, ordered_states, xml_escape};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+const RADIUS: f32 = 22.0;
+const ACCEPTING_RADIUS: f32 = 18.0;
+// Odchylenie środka łuku dla krawędzi w obie strony
+const BEND: f32 = 28.0;
+// Wysokość pętli nad stanem
+const LOOP_HEIGHT: f32 = 70.0;
+const START_ARROW: f32 = 36.0;
+const PADDING: f32 = 30.0;
+const FONT: &str = "font-family=\"DejaVu Sans, Arial, sans-serif\" font-size=\"14\"";
+
+// Diagram automatu jako samodzielny dokument SVG. Przejścia między tą samą
+// parą stanów łączone są w jedną etykietę, krawędzie w obie strony wygięte,
+// a pętle rysowane nad stanem.
+pub fn render(enas: &ENAS, kind: LayoutKind) -> String {
+    let order = ordered_states(enas.get_start_state(), enas.get_states().keys());
+    let positions = Layout::positions(enas, &order, kind);
+    let index = |name: &str| order.iter().position(|s| s == name).unwrap();
+
+    // Obszar rysunku: stany, pętle nad nimi i strzałka startowa
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (0.0f32, 0.0f32, 100.0f32, 100.0f32);
+    if !positions.is_empty() {
+        min_x = positions.iter().map(|p| p.0).fold(f32::MAX, f32::min) - RADIUS - START_ARROW;
+        min_y = positions.iter().map(|p| p.1).fold(f32::MAX, f32::min) - RADIUS - LOOP_HEIGHT;
+        max_x = positions.iter().map(|p| p.0).fold(f32::MIN, f32::max) + RADIUS;
+        max_y = positions.iter().map(|p| p.1).fold(f32::MIN, f32::max) + RADIUS + BEND;
+    }
+    let (x0, y0) = (min_x - PADDING, min_y - PADDING);
+    let (width, height) = (max_x - min_x + 2.0 * PADDING, max_y - min_y + 2.0 * PADDING);
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"{:.1} {:.1} {:.1} {:.1}\">",
+        width, height, x0, y0, width, height
+    )
+    .unwrap();
+    writeln!(svg, "  <defs>").unwrap();
+    writeln!(
+        svg,
+        "    <marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">"
+    )
+    .unwrap();
+    writeln!(svg, "      <path d=\"M0,0 L10,5 L0,10 z\" fill=\"black\"/>").unwrap();
+    writeln!(svg, "    </marker>").unwrap();
+    writeln!(svg, "  </defs>").unwrap();
+    writeln!(svg, "  <rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"white\"/>", x0, y0, width, height).unwrap();
+
+    let edges = edges(enas, &order);
+    let pairs: HashSet<(&str, &str)> = edges.iter().map(|e| (e.from.as_str(), e.to.as_str())).collect();
+    writeln!(svg, "  <g fill=\"none\" stroke=\"black\" stroke-width=\"1.5\">").unwrap();
+    // Stan startowy jest pierwszy w kolejności, o ile w ogóle istnieje
+    if enas.get_states().contains_key(enas.get_start_state()) {
+        let (x, y) = positions[0];
+        writeln!(svg, "    {}", line((x - RADIUS - START_ARROW, y), (x - RADIUS, y))).unwrap();
+    }
+    let mut labels = Vec::new();
+    for edge in &edges {
+        let text = diagram_label(&edge.labels);
+        let (from, to) = (positions[index(&edge.from)], positions[index(&edge.to)]);
+        if edge.from == edge.to {
+            let (x, y) = from;
+            let (sx, sy) = (x - RADIUS * 0.5, y - RADIUS * 0.87);
+            let (ex, ey) = (x + RADIUS * 0.5, y - RADIUS * 0.87);
+            writeln!(
+                svg,
+                "    <path d=\"M{:.1},{:.1} C{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\" marker-end=\"url(#arrow)\"/>",
+                sx,
+                sy,
+                x - 35.0,
+                y - LOOP_HEIGHT,
+                x + 35.0,
+                y - LOOP_HEIGHT,
+                ex,
+                ey
+            )
+            .unwrap();
+            labels.push(((x, y - LOOP_HEIGHT * 0.75 - 10.0), text));
+            continue;
+        }
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let length = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+        let (ux, uy) = (dx / length, dy / length);
+        // Normalna po lewej stronie kierunku (oś y w dół)
+        let (nx, ny) = (uy, -ux);
+        if pairs.contains(&(edge.to.as_str(), edge.from.as_str())) {
+            let control = ((from.0 + to.0) / 2.0 + nx * 2.0 * BEND, (from.1 + to.1) / 2.0 + ny * 2.0 * BEND);
+            let start = towards(from, control, RADIUS);
+            let end = towards(to, control, RADIUS);
+            writeln!(
+                svg,
+                "    <path d=\"M{:.1},{:.1} Q{:.1},{:.1} {:.1},{:.1}\" marker-end=\"url(#arrow)\"/>",
+                start.0, start.1, control.0, control.1, end.0, end.1
+            )
+            .unwrap();
+            let middle = ((from.0 + to.0) / 2.0 + nx * BEND, (from.1 + to.1) / 2.0 + ny * BEND);
+            labels.push(((middle.0 + nx * 14.0, middle.1 + ny * 14.0 + 5.0), text));
+        } else {
+            let start = (from.0 + ux * RADIUS, from.1 + uy * RADIUS);
+            let end = (to.0 - ux * RADIUS, to.1 - uy * RADIUS);
+            writeln!(svg, "    {}", line(start, end)).unwrap();
+            let middle = ((from.0 + to.0) / 2.0, (from.1 + to.1) / 2.0);
+            labels.push(((middle.0 + nx * 14.0, middle.1 + ny * 14.0 + 5.0), text));
+        }
+    }
+    for (i, name) in order.iter().enumerate() {
+        let (x, y) = positions[i];
+        writeln!(svg, "    <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"white\"/>", x, y, RADIUS).unwrap();
+        if enas.get_states()[name].is_accepting() {
+            writeln!(svg, "    <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\"/>", x, y, ACCEPTING_RADIUS).unwrap();
+        }
+    }
+    writeln!(svg, "  </g>").unwrap();
+
+    writeln!(svg, "  <g {} text-anchor=\"middle\" fill=\"black\">", FONT).unwrap();
+    for (i, name) in order.iter().enumerate() {
+        let (x, y) = positions[i];
+        writeln!(svg, "    <text x=\"{:.1}\" y=\"{:.1}\">{}</text>", x, y + 5.0, xml_escape(name)).unwrap();
+    }
+    for ((x, y), text) in labels {
+        writeln!(svg, "    <text x=\"{:.1}\" y=\"{:.1}\">{}</text>", x, y, xml_escape(&text)).unwrap();
+    }
+    writeln!(svg, "  </g>").unwrap();
+    writeln!(svg, "</svg>").unwrap();
+    svg
+}
+
+fn line(from: (f32, f32), to: (f32, f32)) -> String {
+    format!(
+        "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" marker-end=\"url(#arrow)\"/>",
+        from.0, from.1, to.0, to.1
+    )
+}
+
+// Punkt na brzegu koła o środku `center` w kierunku `target`
+fn towards(center: (f32, f32), target: (f32, f32), distance: f32) -> (f32, f32) {
+    let (dx, dy) = (target.0 - center.0, target.1 - center.1);
+    let length = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+    (center.0 + dx / length * distance, center.1 + dy / length * distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::Alphabet::Alphabet;
+    use crate::elements::Node::ENASNode;
+
+    fn automat(start: &str) -> ENAS {
+        let mut enas = ENAS::new(Alphabet::new());
+        enas.add_state(ENASNode::new("q0", true));
+        enas.set_start_state(start);
+        enas
+    }
+
+    #[test]
+    fn start_arrow() {
+        assert!(render(&automat("q0"), LayoutKind::Layered).contains("<line"));
+    }
+
+    #[test]
+    fn no_start_arrow_without_start_state() {
+        assert!(!render(&automat("brak"), LayoutKind::Layered).contains("<line"));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod Layout;
+pub mod Png;
+pub mod Svg;
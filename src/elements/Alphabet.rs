@@ -1,23 +1,135 @@
-use std::collections::HashSet;
+use crate::elements::Symbol::Symbol;
+use std::collections::BTreeSet;
 
-pub struct Alphabet {
-    pub(crate) alphabet: HashSet<char>,
+// Alfabet uporządkowany według porządku symboli – iteracja (a więc i kolejność
+// komunikatów, słów, kolumn w eksportach) jest zawsze taka sama
+#[derive(Clone)]
+pub struct Alphabet<S: Symbol = char> {
+    pub(crate) alphabet: BTreeSet<S>,
 }
 
-impl Alphabet {
+impl<S: Symbol> Alphabet<S> {
     pub fn new() -> Self {
-        Self { alphabet: HashSet::new() }
+        Self { alphabet: BTreeSet::new() }
     }
 
-    pub fn add(&mut self, character: char) {
+    pub fn add(&mut self, character: S) {
         self.alphabet.insert(character);
     }
 
-    pub fn contains(&self, character: &char) -> bool {
+    pub fn remove(&mut self, character: &S) -> bool {
+        self.alphabet.remove(character)
+    }
+
+    pub fn contains(&self, character: &S) -> bool {
         self.alphabet.contains(character)
     }
 
     pub fn len(&self) -> usize {
         self.alphabet.len()
     }
-}
\ No newline at end of file
+
+    pub fn is_empty(&self) -> bool {
+        self.alphabet.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &S> {
+        self.alphabet.iter()
+    }
+
+    pub fn symbols(&self) -> Vec<S> {
+        self.alphabet.iter().cloned().collect()
+    }
+}
+
+impl Alphabet<char> {
+    // Dodaje wszystkie znaki z zapisu klasy (zob. `Alphabet::expand`)
+    pub fn add_class(&mut self, spec: &str) -> Result<(), String> {
+        for c in Self::expand(spec)? {
+            self.add(c);
+        }
+        Ok(())
+    }
+
+    // Największa liczba znaków w jednym zakresie – dość dla całych bloków
+    // Unicode (np. cyrylicy), a zakres od U+0000 do U+10FFFF nie rozwija się
+    // w ponad milion symboli
+    pub const MAX_RANGE: u32 = 4096;
+
+    // Rozwija zapis symboli: pojedynczy znak ("a"), zakres ("a-z") albo klasę
+    // w nawiasach kwadratowych łączącą zakresy i znaki ("[0-9_]")
+    pub fn expand(spec: &str) -> Result<Vec<char>, String> {
+        let chars: Vec<char> = spec.trim().chars().collect();
+        let items = match chars.as_slice() {
+            [] => return Err("Pusty zapis symbolu.".to_string()),
+            [c] => return Ok(vec![*c]),
+            ['[', inner @ .., ']'] if !inner.is_empty() => inner,
+            [_, '-', _] => chars.as_slice(),
+            _ => {
+                return Err(format!(
+                    "Nieprawidłowy zapis symbolu '{}' – użyj znaku, zakresu (a-z) lub klasy ([0-9]).",
+                    spec.trim()
+                ))
+            }
+        };
+
+        let mut symbols = BTreeSet::new();
+        let mut i = 0;
+        while i < items.len() {
+            if i + 2 < items.len() && items[i + 1] == '-' {
+                let (from, to) = (items[i], items[i + 2]);
+                if from > to {
+                    return Err(format!("Odwrócony zakres '{}-{}'.", from, to));
+                }
+                if to as u32 - from as u32 >= Self::MAX_RANGE {
+                    return Err(format!(
+                        "Zakres '{}-{}' jest zbyt duży – najwyżej {} symboli.",
+                        from.escape_debug(),
+                        to.escape_debug(),
+                        Self::MAX_RANGE
+                    ));
+                }
+                symbols.extend(from..=to);
+                i += 3;
+            } else {
+                symbols.insert(items[i]);
+                i += 1;
+            }
+        }
+        Ok(symbols.into_iter().collect())
+    }
+}
+
+impl<S: Symbol> Default for Alphabet<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, S: Symbol> IntoIterator for &'a Alphabet<S> {
+    type Item = &'a S;
+    type IntoIter = std::collections::btree_set::Iter<'a, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.alphabet.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_ranges_and_classes() {
+        assert_eq!(Alphabet::expand("a-c").unwrap(), ['a', 'b', 'c']);
+        assert_eq!(Alphabet::expand("[0-2_]").unwrap(), ['0', '1', '2', '_']);
+        assert!(Alphabet::expand("c-a").is_err());
+    }
+
+    #[test]
+    fn range_size_is_capped() {
+        let largest = char::from_u32(Alphabet::MAX_RANGE - 1).unwrap();
+        assert_eq!(Alphabet::expand(&format!("\0-{}", largest)).unwrap().len(), Alphabet::MAX_RANGE as usize);
+        assert!(Alphabet::expand("\0-\u{10FFFF}").is_err());
+    }
+}
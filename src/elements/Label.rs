@@ -0,0 +1,48 @@
+use crate::elements::Symbol::EPSILON;
+use std::fmt;
+
+// Etykieta przejścia ENAS: symbol alfabetu albo przejście ε.
+// Dzięki osobnemu wariantowi ε nie koliduje z żadnym symbolem alfabetu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Label<S = char> {
+    Symbol(S),
+    Epsilon,
+}
+
+impl<S> Label<S> {
+    pub fn symbol(&self) -> Option<&S> {
+        match self {
+            Label::Symbol(symbol) => Some(symbol),
+            Label::Epsilon => None,
+        }
+    }
+
+    pub fn is_epsilon(&self) -> bool {
+        matches!(self, Label::Epsilon)
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for Label<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Label::Symbol(symbol) => write!(f, "{}", symbol),
+            Label::Epsilon => write!(f, "{}", EPSILON),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn epsilon_differs_from_reserved_symbol() {
+        let symbol = Label::Symbol('ε');
+        assert_ne!(symbol, Label::Epsilon);
+        assert!(!symbol.is_epsilon());
+        assert_eq!(symbol.symbol(), Some(&'ε'));
+        assert_eq!(Label::<char>::Epsilon.symbol(), None);
+        // Oba zapisują się tak samo – stąd zakaz takiego symbolu w alfabecie
+        assert_eq!(symbol.to_string(), Label::<char>::Epsilon.to_string());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::elements::Label::Label;
+use crate::elements::Symbol::Symbol;
 use std::collections::HashMap;
 
 // Część wspólna dla wszystkich węzłów
@@ -5,6 +7,8 @@ use std::collections::HashMap;
 pub struct NodeCommon {
     name: String,
     accepting: bool,
+    // Położenie stanu na diagramie (np. z pliku JFLAP); None – brak układu
+    position: Option<(f32, f32)>,
 }
 
 impl NodeCommon {
@@ -12,6 +16,7 @@ impl NodeCommon {
         Self {
             name: name.to_string(),
             accepting,
+            position: None,
         }
     }
 
@@ -22,22 +27,31 @@ impl NodeCommon {
     pub fn is_accepting(&self) -> bool {
         self.accepting
     }
+
+    pub fn get_position(&self) -> Option<(f32, f32)> {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: Option<(f32, f32)>) {
+        self.position = position;
+    }
 }
 
 // Trait z metodami wspólnymi
 pub trait NodeBase {
     fn get_name(&self) -> &str;
     fn is_accepting(&self) -> bool;
+    fn get_position(&self) -> Option<(f32, f32)>;
 }
 
 // DASNode – deterministyczny
 #[derive(Clone)]
-pub struct DASNode {
+pub struct DASNode<S: Symbol = char> {
     common: NodeCommon,
-    connections: HashMap<char, String>,
+    connections: HashMap<S, String>,
 }
 
-impl DASNode {
+impl<S: Symbol> DASNode<S> {
     pub fn new(name: &str, accepting: bool) -> Self {
         Self {
             common: NodeCommon::new(name, accepting),
@@ -45,32 +59,39 @@ impl DASNode {
         }
     }
 
-    pub fn add_connection(&mut self, symbol: char, state_name: &str) {
+    pub fn add_connection(&mut self, symbol: S, state_name: &str) {
         self.connections.insert(symbol, state_name.to_string());
     }
 
-    pub fn get_connections(&self) -> &HashMap<char, String> {
+    pub fn get_connections(&self) -> &HashMap<S, String> {
         &self.connections
     }
+
+    pub fn set_position(&mut self, position: Option<(f32, f32)>) {
+        self.common.set_position(position);
+    }
 }
 
-impl NodeBase for DASNode {
+impl<S: Symbol> NodeBase for DASNode<S> {
     fn get_name(&self) -> &str {
         self.common.get_name()
     }
     fn is_accepting(&self) -> bool {
         self.common.is_accepting()
     }
+    fn get_position(&self) -> Option<(f32, f32)> {
+        self.common.get_position()
+    }
 }
 
 // ENASNode – niedeterministyczny z epsilon
 #[derive(Clone)]
-pub struct ENASNode {
+pub struct ENASNode<S: Symbol = char> {
     common: NodeCommon,
-    connections: HashMap<char, Vec<String>>,
+    connections: HashMap<Label<S>, Vec<String>>,
 }
 
-impl ENASNode {
+impl<S: Symbol> ENASNode<S> {
     pub fn new(name: &str, accepting: bool) -> Self {
         Self {
             common: NodeCommon::new(name, accepting),
@@ -78,20 +99,27 @@ impl ENASNode {
         }
     }
 
-    pub fn add_connection(&mut self, symbol: char, state_names: Vec<String>) {
-        self.connections.insert(symbol, state_names);
+    pub fn add_connection(&mut self, label: Label<S>, state_names: Vec<String>) {
+        self.connections.insert(label, state_names);
     }
 
-    pub fn get_connections(&self) -> &HashMap<char, Vec<String>> {
+    pub fn get_connections(&self) -> &HashMap<Label<S>, Vec<String>> {
         &self.connections
     }
+
+    pub fn set_position(&mut self, position: Option<(f32, f32)>) {
+        self.common.set_position(position);
+    }
 }
 
-impl NodeBase for ENASNode {
+impl<S: Symbol> NodeBase for ENASNode<S> {
     fn get_name(&self) -> &str {
         self.common.get_name()
     }
     fn is_accepting(&self) -> bool {
         self.common.is_accepting()
     }
+    fn get_position(&self) -> Option<(f32, f32)> {
+        self.common.get_position()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,152 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, OnceLock};
+
+// Zapis zarezerwowany dla ε – symbol o takim zapisie nie może należeć do alfabetu
+pub const EPSILON: &str = "ε";
+
+// Symbol alfabetu automatu. Gotowe implementacje: `char`, `u8` (bajty)
+// oraz `Token` (internowane napisy, np. słowa kluczowe `if`, `else`, `id`).
+pub trait Symbol: Clone + Eq + Hash + Ord + fmt::Debug + fmt::Display {
+    // Czy symbol wyglądałby jak ε (w słowach i w tabeli GUI)
+    fn is_reserved(&self) -> bool {
+        self.to_string() == EPSILON
+    }
+
+    // Zapis słowa złożonego z symboli; domyślnie symbole oddzielone spacją
+    fn format_word(word: &[Self]) -> String {
+        word.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(" ")
+    }
+
+    // Bajt wejścia odpowiadający symbolowi (szybka ścieżka `CompiledDas::matches`)
+    fn as_byte(&self) -> Option<u8> {
+        None
+    }
+}
+
+impl Symbol for char {
+    fn as_byte(&self) -> Option<u8> {
+        self.is_ascii().then_some(*self as u8)
+    }
+
+    fn format_word(word: &[Self]) -> String {
+        word.iter().collect()
+    }
+}
+
+impl Symbol for u8 {
+    fn as_byte(&self) -> Option<u8> {
+        Some(*self)
+    }
+}
+
+fn interner() -> &'static Mutex<HashMap<&'static str, u32>> {
+    static INTERNER: OnceLock<Mutex<HashMap<&'static str, u32>>> = OnceLock::new();
+    INTERNER.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// Internowany napis: kopiowanie, porównanie i haszowanie działają na
+// indeksie, a porządek (np. shortlex) wynika z samego tekstu. Token pamięta
+// też swój tekst, więc ani porządek, ani wypisywanie nie blokują internera.
+#[derive(Clone, Copy)]
+pub struct Token {
+    id: u32,
+    name: &'static str,
+}
+
+impl Token {
+    pub fn new(name: &str) -> Self {
+        let mut ids = interner().lock().unwrap();
+        if let Some((&name, &id)) = ids.get_key_value(name) {
+            return Token { id, name };
+        }
+        // Napisy żyją do końca programu – tokenów jest niewiele
+        let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+        let id = ids.len() as u32;
+        ids.insert(name, id);
+        Token { id, name }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl PartialEq for Token {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Token {}
+
+impl Hash for Token {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Ord for Token {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.id == other.id {
+            return Ordering::Equal;
+        }
+        self.name.cmp(other.name)
+    }
+}
+
+impl PartialOrd for Token {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Token({:?})", self.as_str())
+    }
+}
+
+impl Symbol for Token {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interning_returns_same_token() {
+        let a = Token::new("while");
+        let b = Token::new(&String::from("while"));
+        assert_eq!(a, b);
+        assert_eq!(a.id, b.id);
+        assert!(std::ptr::eq(a.as_str(), b.as_str()));
+        assert_ne!(Token::new("whilst"), a);
+    }
+
+    #[test]
+    fn tokens_sort_by_text() {
+        // Kolejność internowania nie wpływa na porządek
+        let mut tokens = [Token::new("zz"), Token::new("id"), Token::new("else"), Token::new("if")];
+        tokens.sort();
+        let names: Vec<&str> = tokens.iter().map(Token::as_str).collect();
+        assert_eq!(names, ["else", "id", "if", "zz"]);
+    }
+
+    #[test]
+    fn token_text() {
+        let token = Token::new("ε");
+        assert_eq!(token.as_str(), "ε");
+        assert_eq!(token.to_string(), "ε");
+        assert_eq!(format!("{:?}", token), "Token(\"ε\")");
+        assert!(token.is_reserved());
+        assert!(!Token::new("eps").is_reserved());
+    }
+}
@@ -1,2 +1,4 @@
 pub mod Alphabet;
-pub mod Node;
\ No newline at end of file
+pub mod Label;
+pub mod Node;
+pub mod Symbol;
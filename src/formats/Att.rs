@@ -0,0 +1,288 @@
+use crate::automats::ENAS::ENAS;
+use crate::elements::Alphabet::Alphabet;
+use crate::elements::Label::Label;
+use crate::elements::Node::{ENASNode, NodeBase};
+use crate::elements::Symbol::EPSILON;
+use crate::formats::{ordered_states, Automat};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+
+// Symbol ε w tabelach symboli OpenFst (zawsze o identyfikatorze 0)
+const EPS: &str = "<eps>";
+const SPACE: &str = "<space>";
+
+// Automat w formacie tekstowym AT&T (jak dla `fstcompile --acceptor`) z osobnymi
+// tabelami symboli wejściowych i nazw stanów
+pub struct AttFiles {
+    pub fst: String,
+    pub isymbols: String,
+    pub ssymbols: String,
+}
+
+// Łuki "src dst etykieta" (dalsze kolumny – etykieta wyjściowa, waga – są
+// pomijane) i wiersze stanów akceptujących "stan [waga]"; stan startowy to stan
+// z pierwszego wiersza. Etykiety liczbowe są zawsze identyfikatorami: z tabelą
+// symboli oznaczają wpisany w niej symbol, bez niej – kod Unicode (0 to ε, jak
+// w pynini). Pozostałe etykiety to nazwy: pojedyncze znaki, <eps> albo <space>.
+pub fn read(fst: &str, isymbols: Option<&str>, ssymbols: Option<&str>) -> Result<Automat, String> {
+    let isymbols = isymbols.map(|t| symbol_table(t, "symboli wejściowych")).transpose()?;
+    let ssymbols = ssymbols.map(|t| symbol_table(t, "nazw stanów")).transpose()?;
+
+    let lines: Vec<(usize, Vec<&str>)> = fst
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.split_whitespace().collect::<Vec<_>>()))
+        .filter(|(_, fields)| !fields.is_empty())
+        .collect();
+    let Some((_, first)) = lines.first() else {
+        return Err("Pusty plik automatu.".to_string());
+    };
+
+    // Nazwy stanów z tabeli; powtórzone nazwy dostają numer jako przyrostek,
+    // a gdy i taka nazwa jest zajęta, także kolejny numer (jak w JFLAP)
+    let state_names: Option<HashMap<String, String>> = ssymbols.map(|table| {
+        let mut used: HashSet<String> = HashSet::new();
+        let mut names = HashMap::new();
+        for (name, id) in &table {
+            let mut unique = name.clone();
+            let mut suffix = 1;
+            while !used.insert(unique.clone()) {
+                unique = if suffix == 1 { format!("{}_{}", name, id) } else { format!("{}_{}_{}", name, id, suffix) };
+                suffix += 1;
+            }
+            names.insert(id.to_string(), unique.clone());
+            names.entry(name.clone()).or_insert(unique);
+        }
+        names
+    });
+    let state = |line: usize, token: &str| -> Result<String, String> {
+        match &state_names {
+            Some(names) => names
+                .get(token)
+                .cloned()
+                .ok_or_else(|| format!("Wiersz {}: stan '{}' nie występuje w tabeli nazw stanów.", line, token)),
+            None if token.parse::<u64>().is_ok() => Ok(format!("q{}", token)),
+            None => Ok(token.to_string()),
+        }
+    };
+
+    let input_names: Option<HashMap<u64, String>> =
+        isymbols.as_ref().map(|table| table.iter().map(|(name, id)| (*id, name.clone())).collect());
+    let label = |line: usize, token: &str| -> Result<Label, String> {
+        let name = match (token.parse::<u64>(), &input_names) {
+            (Ok(id), Some(names)) => names
+                .get(&id)
+                .ok_or_else(|| format!("Wiersz {}: identyfikator {} nie występuje w tabeli symboli.", line, id))?,
+            (Ok(0), None) => return Ok(Label::Epsilon),
+            (Ok(code), None) => {
+                return u32::try_from(code)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .map(Label::Symbol)
+                    .ok_or_else(|| format!("Wiersz {}: {} nie jest kodem znaku Unicode.", line, code));
+            }
+            (Err(_), Some(_)) if !isymbols.iter().flatten().any(|(name, _)| name == token) => {
+                return Err(format!("Wiersz {}: etykieta '{}' nie występuje w tabeli symboli.", line, token));
+            }
+            (Err(_), _) => token,
+        };
+        symbol_label(name).map_err(|e| format!("Wiersz {}: {}", line, e))
+    };
+
+    let mut alphabet = Alphabet::new();
+    if let Some(table) = &isymbols {
+        for (name, _) in table {
+            if let Label::Symbol(c) = symbol_label(name)? {
+                alphabet.add(c);
+            }
+        }
+    }
+    let start = state(lines[0].0, first[0])?;
+    let mut accepting: BTreeMap<String, bool> = BTreeMap::new();
+    let mut connections: BTreeMap<(String, Label), Vec<String>> = BTreeMap::new();
+    accepting.insert(start.clone(), false);
+    for (line, fields) in &lines {
+        let from = state(*line, fields[0])?;
+        if fields.len() >= 3 {
+            let to = state(*line, fields[1])?;
+            let label = label(*line, fields[2])?;
+            if let Label::Symbol(c) = label {
+                alphabet.add(c);
+            }
+            accepting.entry(from.clone()).or_insert(false);
+            accepting.entry(to.clone()).or_insert(false);
+            let targets = connections.entry((from, label)).or_default();
+            if !targets.contains(&to) {
+                targets.push(to);
+            }
+        } else {
+            // Waga nieskończona (zero w półpierścieniu tropikalnym) – stan nieakceptujący
+            let infinite = fields.get(1).is_some_and(|w| w.eq_ignore_ascii_case("infinity") || w.eq_ignore_ascii_case("inf"));
+            let final_state = accepting.entry(from).or_insert(false);
+            *final_state |= !infinite;
+        }
+    }
+
+    let mut enas = ENAS::new(alphabet);
+    enas.set_start_state(&start);
+    let mut nodes: HashMap<String, ENASNode> = accepting
+        .into_iter()
+        .map(|(name, accepting)| (name.clone(), ENASNode::new(&name, accepting)))
+        .collect();
+    for ((from, label), targets) in connections {
+        nodes.get_mut(&from).unwrap().add_connection(label, targets);
+    }
+    for node in nodes.into_values() {
+        enas.add_state(node);
+    }
+    Ok(Automat::from_enas(enas))
+}
+
+// Stany numerowane od 0 (startowy) w kolejności nazw. Łuki mają etykiety
+// liczbowe – kody Unicode (0 to ε) – a nazwy symboli są tylko w tabeli, więc
+// plik wczytuje się tak samo z tabelą symboli i bez niej (jak `fstprint` bez
+// --isymbols; nazwy dołącza np. `fstdraw --isymbols=automat.isyms`)
+pub fn write(enas: &ENAS) -> AttFiles {
+    let order = ordered_states(enas.get_start_state(), enas.get_states().keys());
+    let ids: HashMap<&str, usize> = order.iter().enumerate().map(|(i, s)| (s.as_str(), i)).collect();
+
+    let mut fst = String::new();
+    for (i, name) in order.iter().enumerate() {
+        let node = &enas.get_states()[name];
+        let mut connections: Vec<_> = node.get_connections().iter().collect();
+        connections.sort();
+        let mut arcs = 0;
+        for (label, targets) in connections {
+            let label = match label {
+                Label::Symbol(c) => *c as u32,
+                Label::Epsilon => 0,
+            };
+            for target in targets.iter().filter_map(|t| ids.get(t.as_str())) {
+                writeln!(fst, "{}\t{}\t{}", i, target, label).unwrap();
+                arcs += 1;
+            }
+        }
+        if node.is_accepting() {
+            writeln!(fst, "{}", i).unwrap();
+        } else if i == 0 && arcs == 0 {
+            // Pierwszy wiersz wyznacza stan startowy – bez łuków zapisywany
+            // jest jako nieakceptujący (waga nieskończona)
+            writeln!(fst, "0\tInfinity").unwrap();
+        }
+    }
+
+    let mut isymbols = format!("{}\t0\n", EPS);
+    for c in enas.alphabet().iter() {
+        writeln!(isymbols, "{}\t{}", symbol_name(*c), *c as u32).unwrap();
+    }
+    // Tabele symboli nie dopuszczają białych znaków w nazwach
+    let mut ssymbols = String::new();
+    for (i, name) in order.iter().enumerate() {
+        let name: String = name.chars().map(|c| if c.is_whitespace() { '_' } else { c }).collect();
+        writeln!(ssymbols, "{}\t{}", name, i).unwrap();
+    }
+    AttFiles { fst, isymbols, ssymbols }
+}
+
+// Wiersze "nazwa numer" tabeli symboli OpenFst
+fn symbol_table(text: &str, kind: &str) -> Result<Vec<(String, u64)>, String> {
+    let mut table = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            [] => {}
+            [name, id] => {
+                let id = id
+                    .parse()
+                    .map_err(|_| format!("Wiersz {} tabeli {}: niepoprawny numer '{}'.", i + 1, kind, id))?;
+                table.push((name.to_string(), id));
+            }
+            _ => return Err(format!("Wiersz {} tabeli {}: oczekiwano 'nazwa numer'.", i + 1, kind)),
+        }
+    }
+    Ok(table)
+}
+
+// Znak jako nazwa symbolu: spacja jako <space>, inne białe i sterujące jako <U+XXXX>
+fn symbol_name(c: char) -> String {
+    match c {
+        ' ' => SPACE.to_string(),
+        c if c.is_whitespace() || c.is_control() => format!("<U+{:04X}>", c as u32),
+        c => c.to_string(),
+    }
+}
+
+fn symbol_label(name: &str) -> Result<Label, String> {
+    if name == EPS || name == EPSILON {
+        return Ok(Label::Epsilon);
+    }
+    if name == SPACE {
+        return Ok(Label::Symbol(' '));
+    }
+    if let Some(hex) = name.strip_prefix("<U+").and_then(|h| h.strip_suffix('>'))
+        && let Some(c) = u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+    {
+        return Ok(Label::Symbol(c));
+    }
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(Label::Symbol(c)),
+        _ => Err(format!("symbol '{}' nie jest pojedynczym znakiem – obsługiwane są tylko automaty na znakach.", name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::Dsl;
+
+    fn automat(text: &str) -> ENAS {
+        Dsl::parse(text).unwrap().to_enas()
+    }
+
+    fn round_trip(enas: &ENAS) {
+        let files = write(enas);
+        let expected = Dsl::write(enas);
+        let bare = read(&files.fst, None, None).unwrap().to_enas();
+        assert_eq!(Dsl::write(&bare), expected);
+        let tables = read(&files.fst, Some(&files.isymbols), Some(&files.ssymbols)).unwrap().to_enas();
+        assert_eq!(Dsl::write(&tables), expected);
+    }
+
+    #[test]
+    fn digit_alphabet_round_trip() {
+        round_trip(&automat("alphabet 0 1\nstart q0\naccept q1\nq0 -0-> q0\nq0 -1-> q1\nq1 -0,1-> q1"));
+    }
+
+    #[test]
+    fn epsilon_and_space_round_trip() {
+        round_trip(&automat("start q0\naccept q2\nq0 -ε-> q1\nq1 -' '-> q2\nq1 -0-> q0, q2"));
+    }
+
+    #[test]
+    fn numeric_labels_use_symbol_table() {
+        let automat = read("0 1 5\n1", Some("<eps> 0\n0 5"), None).unwrap().to_enas();
+        assert!(automat.accepts("0".chars()));
+    }
+
+    #[test]
+    fn numeric_labels_without_table_are_code_points() {
+        let automat = read("0 1 48\n1 2 0\n2", None, None).unwrap().to_enas();
+        assert!(automat.accepts("0".chars()));
+        assert!(!automat.accepts("".chars()));
+    }
+
+    #[test]
+    fn duplicate_state_names_become_unique() {
+        let enas = read("0 1 97\n1 2 97\n2 3 97\n3", None, Some("q 0\nq 1\nq_1 2\nq_1 3")).unwrap().to_enas();
+        let mut names: Vec<&String> = enas.get_states().keys().collect();
+        names.sort();
+        assert_eq!(names, ["q", "q_1", "q_1_2", "q_1_3"]);
+    }
+
+    #[test]
+    fn unknown_label_is_an_error() {
+        assert!(read("0 1 7\n1", Some("<eps> 0\na 1"), None).is_err());
+        assert!(read("0 1 b\n1", Some("<eps> 0\na 1"), None).is_err());
+    }
+}
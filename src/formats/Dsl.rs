@@ -0,0 +1,508 @@
+use crate::automats::ENAS::ENAS;
+use crate::elements::Alphabet::Alphabet;
+use crate::elements::Label::Label;
+use crate::elements::Node::{ENASNode, NodeBase};
+use crate::elements::Symbol::{Symbol, EPSILON};
+use crate::formats::{ordered_states, Automat};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fmt::Write;
+
+// Tekstowy zapis automatu, instrukcje oddzielone średnikiem albo końcem wiersza:
+//
+//   alphabet a b c      # symbole, zakresy (a-z) i klasy ([0-9_]); opcjonalne
+//   start q0
+//   accept q2 q3
+//   q0 -a-> q1
+//   q0 -b,c-> q2        # kilka symboli na jednej krawędzi
+//   q1 -ε-> q2, q3      # ε (albo eps) i kilka stanów docelowych
+//
+// Nazwy stanów ze znakami specjalnymi zapisuje się w cudzysłowie ("q 1"),
+// a symbole specjalne w apostrofach (' ', '-', ';'). Bez instrukcji
+// `alphabet` alfabetem są symbole użyte w przejściach.
+
+const KEYWORDS: [&str; 3] = ["alphabet", "start", "accept"];
+const EPSILON_WORDS: [&str; 2] = [EPSILON, "eps"];
+// Znaki kończące nazwę stanu albo symbol zapisany bez cudzysłowu
+const SPECIAL: [char; 6] = [';', ',', '#', '"', '\'', '-'];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Wiersz {}, kolumna {}: {}", self.line, self.column, self.message)
+    }
+}
+
+pub fn parse(text: &str) -> Result<Automat, ParseError> {
+    Parser { chars: text.chars().collect(), pos: 0 }.program()
+}
+
+// Postać kanoniczna: alfabet, stan startowy, akceptujące i przejścia w kolejności
+// stanów; symbole prowadzące do tych samych stanów łączone są w jedną krawędź
+pub fn write(enas: &ENAS) -> String {
+    let order = ordered_states(enas.get_start_state(), enas.get_states().keys());
+    let mut text = String::new();
+    let symbols = enas.alphabet().symbols();
+    if !symbols.is_empty() {
+        writeln!(text, "alphabet {}", compress(&symbols).join(" ")).unwrap();
+    }
+    // Bez stanu startowego (pusty automat) instrukcja `start` jest pomijana
+    if !enas.get_start_state().is_empty() {
+        writeln!(text, "start {}", quote_name(enas.get_start_state())).unwrap();
+    }
+    let accepting: Vec<String> = order
+        .iter()
+        .filter(|s| enas.get_states()[*s].is_accepting())
+        .map(|s| quote_name(s))
+        .collect();
+    if !accepting.is_empty() {
+        writeln!(text, "accept {}", accepting.join(" ")).unwrap();
+    }
+    for name in &order {
+        let mut by_targets: BTreeMap<Vec<String>, Vec<Label>> = BTreeMap::new();
+        for (label, targets) in enas.get_states()[name].get_connections() {
+            if !targets.is_empty() {
+                by_targets.entry(targets.clone()).or_default().push(*label);
+            }
+        }
+        let mut edges: Vec<(Vec<Label>, Vec<String>)> = by_targets
+            .into_iter()
+            .map(|(targets, mut labels)| {
+                labels.sort();
+                (labels, targets)
+            })
+            .collect();
+        edges.sort();
+        for (labels, targets) in edges {
+            let symbols: Vec<char> = labels.iter().filter_map(|l| l.symbol().copied()).collect();
+            let mut written = compress(&symbols);
+            if labels.contains(&Label::Epsilon) {
+                written.push(EPSILON.to_string());
+            }
+            let targets: Vec<String> = targets.iter().map(|t| quote_name(t)).collect();
+            writeln!(text, "{} -{}-> {}", quote_name(name), written.join(","), targets.join(", ")).unwrap();
+        }
+    }
+    text
+}
+
+// Formatowanie tekstu: parsowanie i zapis w postaci kanonicznej
+pub fn format(text: &str) -> Result<String, ParseError> {
+    Ok(write(&parse(text)?.to_enas()))
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+// Przejście z etykietami (z pozycjami w tekście) i stanami docelowymi
+struct Transition {
+    labels: Vec<(Label, usize)>,
+    targets: Vec<String>,
+}
+
+#[derive(Default)]
+struct State {
+    accepting: bool,
+    connections: BTreeMap<Label, Vec<String>>,
+}
+
+impl Parser {
+    fn program(&mut self) -> Result<Automat, ParseError> {
+        let mut alphabet: Option<BTreeSet<char>> = None;
+        let mut start: Option<String> = None;
+        let mut states: BTreeMap<String, State> = BTreeMap::new();
+        // Symbole użyte w przejściach z pozycją pierwszego wystąpienia
+        let mut used: BTreeMap<char, usize> = BTreeMap::new();
+
+        loop {
+            self.skip_blank();
+            match self.peek() {
+                None => break,
+                Some(';' | '\n') => {
+                    self.pos += 1;
+                    continue;
+                }
+                _ => {}
+            }
+            let statement = self.pos;
+            let quoted = self.peek() == Some('"');
+            let word = self.name()?;
+            self.skip_blank();
+            if self.peek() == Some('-') {
+                let Transition { labels, targets } = self.transition()?;
+                for target in &targets {
+                    states.entry(target.clone()).or_default();
+                }
+                let state = states.entry(word).or_default();
+                for (label, position) in labels {
+                    if let Label::Symbol(c) = label {
+                        used.entry(c).or_insert(position);
+                    }
+                    let existing = state.connections.entry(label).or_default();
+                    for target in &targets {
+                        if !existing.contains(target) {
+                            existing.push(target.clone());
+                        }
+                    }
+                }
+            } else if quoted || !KEYWORDS.contains(&word.as_str()) {
+                return Err(self.error_here(format!("Oczekiwano przejścia '-symbol->' po nazwie stanu '{}'.", word)));
+            } else if word == "alphabet" {
+                if alphabet.is_some() {
+                    return Err(self.error_at(statement, "Alfabet zadeklarowano już wcześniej.".to_string()));
+                }
+                alphabet = Some(self.symbols()?);
+            } else if word == "start" {
+                if start.is_some() {
+                    return Err(self.error_at(statement, "Stan startowy zadeklarowano już wcześniej.".to_string()));
+                }
+                let name = self.name()?;
+                states.entry(name.clone()).or_default();
+                start = Some(name);
+            } else {
+                for name in self.names()? {
+                    states.entry(name).or_default().accepting = true;
+                }
+            }
+            self.skip_blank();
+            match self.peek() {
+                None | Some(';' | '\n') => {}
+                Some(_) => return Err(self.error_here("Oczekiwano ';' albo końca wiersza.".to_string())),
+            }
+        }
+
+        // Tylko automat bez stanów może nie mieć stanu startowego
+        let start = match start {
+            Some(start) => start,
+            None if states.is_empty() => String::new(),
+            None => return Err(self.error_here("Brak instrukcji 'start <stan>'.".to_string())),
+        };
+        let alphabet = match alphabet {
+            Some(declared) => {
+                if let Some((c, &position)) = used.iter().find(|(c, _)| !declared.contains(c)) {
+                    return Err(self.error_at(position, format!("Symbol '{}' nie należy do zadeklarowanego alfabetu.", c)));
+                }
+                declared
+            }
+            None => used.keys().copied().collect(),
+        };
+
+        let mut enas = ENAS::new(Alphabet { alphabet });
+        enas.set_start_state(&start);
+        for (name, state) in states {
+            let mut node = ENASNode::new(&name, state.accepting);
+            for (label, targets) in state.connections {
+                node.add_connection(label, targets);
+            }
+            enas.add_state(node);
+        }
+        Ok(Automat::from_enas(enas))
+    }
+
+    // "-a,b-> q1, q2"
+    fn transition(&mut self) -> Result<Transition, ParseError> {
+        self.pos += 1;
+        let mut labels = Vec::new();
+        loop {
+            self.skip_spaces();
+            let position = self.pos;
+            if self.peek() == Some('\'') {
+                labels.push((Label::Symbol(self.quoted_char()?), position));
+            } else {
+                let word = self.word(|p| p.peek() == Some(',') || p.at_arrow_end());
+                if word.is_empty() {
+                    return Err(self.error_here("Oczekiwano symbolu przejścia.".to_string()));
+                }
+                if EPSILON_WORDS.contains(&word.as_str()) {
+                    labels.push((Label::Epsilon, position));
+                } else {
+                    let symbols = Alphabet::expand(&word).map_err(|e| self.error_at(position, e))?;
+                    labels.extend(symbols.into_iter().map(|c| (Label::Symbol(c), position)));
+                }
+            }
+            self.skip_spaces();
+            if self.peek() == Some(',') {
+                self.pos += 1;
+            } else if self.at_arrow_end() {
+                self.pos += 2;
+                break;
+            } else {
+                return Err(self.error_here("Oczekiwano ',' albo '->'.".to_string()));
+            }
+        }
+        let targets = self.names()?;
+        if targets.is_empty() {
+            return Err(self.error_here("Oczekiwano stanu docelowego.".to_string()));
+        }
+        Ok(Transition { labels, targets })
+    }
+
+    // Nazwy stanów oddzielone spacjami lub przecinkami, do końca instrukcji
+    fn names(&mut self) -> Result<Vec<String>, ParseError> {
+        let mut names = Vec::new();
+        loop {
+            self.skip_blank();
+            match self.peek() {
+                None | Some(';' | '\n') => return Ok(names),
+                Some(',') if !names.is_empty() => self.pos += 1,
+                _ => names.push(self.name()?),
+            }
+        }
+    }
+
+    // Symbole alfabetu oddzielone spacjami, do końca instrukcji
+    fn symbols(&mut self) -> Result<BTreeSet<char>, ParseError> {
+        let mut symbols = BTreeSet::new();
+        loop {
+            self.skip_blank();
+            let position = self.pos;
+            match self.peek() {
+                None | Some(';' | '\n') => return Ok(symbols),
+                Some('\'') => {
+                    symbols.insert(self.quoted_char()?);
+                }
+                _ => {
+                    let word = self.word(|p| matches!(p.peek(), Some(';' | '#')));
+                    if EPSILON_WORDS.contains(&word.as_str()) {
+                        return Err(self.error_at(position, format!("{} nie może należeć do alfabetu.", EPSILON)));
+                    }
+                    symbols.extend(Alphabet::expand(&word).map_err(|e| self.error_at(position, e))?);
+                }
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        if self.peek() == Some('"') {
+            self.pos += 1;
+            let mut name = String::new();
+            loop {
+                match self.next() {
+                    Some('"') => break,
+                    Some('\\') => name.push(self.escaped()?),
+                    Some('\n') | None => return Err(self.error_at(start, "Niezamknięty cudzysłów.".to_string())),
+                    Some(c) => name.push(c),
+                }
+            }
+            if name.is_empty() {
+                return Err(self.error_at(start, "Pusta nazwa stanu.".to_string()));
+            }
+            return Ok(name);
+        }
+        let name = self.word(|p| p.peek().is_some_and(|c| SPECIAL.contains(&c)));
+        if name.is_empty() {
+            return Err(self.error_here("Oczekiwano nazwy stanu.".to_string()));
+        }
+        Ok(name)
+    }
+
+    // 'x' – dowolny znak, także specjalny; \' \\ \n \t jako sekwencje ucieczki
+    fn quoted_char(&mut self) -> Result<char, ParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let c = match self.next() {
+            Some('\\') => self.escaped()?,
+            Some('\'') | Some('\n') | None => return Err(self.error_at(start, "Pusty symbol w apostrofach.".to_string())),
+            Some(c) => c,
+        };
+        if self.next() != Some('\'') {
+            return Err(self.error_at(start, "Symbol w apostrofach musi być pojedynczym znakiem.".to_string()));
+        }
+        if c.is_reserved() {
+            return Err(self.error_at(start, format!("{} nie może należeć do alfabetu.", EPSILON)));
+        }
+        Ok(c)
+    }
+
+    fn escaped(&mut self) -> Result<char, ParseError> {
+        match self.next() {
+            Some('n') => Ok('\n'),
+            Some('t') => Ok('\t'),
+            Some(c @ ('\\' | '\'' | '"')) => Ok(c),
+            _ => Err(self.error_at(self.pos - 1, "Nieznana sekwencja ucieczki.".to_string())),
+        }
+    }
+
+    // Znaki do białego znaku albo warunku `stop`
+    fn word(&mut self, stop: impl Fn(&Parser) -> bool) -> String {
+        let mut word = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || stop(self) {
+                break;
+            }
+            word.push(c);
+            self.pos += 1;
+        }
+        word
+    }
+
+    fn at_arrow_end(&self) -> bool {
+        self.peek() == Some('-') && self.chars.get(self.pos + 1) == Some(&'>')
+    }
+
+    // Spacje i komentarze do końca wiersza (bez znaku nowej linii)
+    fn skip_blank(&mut self) {
+        loop {
+            self.skip_spaces();
+            if self.peek() != Some('#') {
+                return;
+            }
+            while self.peek().is_some_and(|c| c != '\n') {
+                self.pos += 1;
+            }
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace() && c != '\n') {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    fn error_here(&self, message: String) -> ParseError {
+        self.error_at(self.pos, message)
+    }
+
+    fn error_at(&self, pos: usize, message: String) -> ParseError {
+        let before = &self.chars[..pos.min(self.chars.len())];
+        let line = before.iter().filter(|&&c| c == '\n').count() + 1;
+        let column = before.iter().rev().take_while(|&&c| c != '\n').count() + 1;
+        ParseError { line, column, message }
+    }
+}
+
+// Symbole do zapisu; ciągi co najmniej trzech kolejnych liter lub cyfr jako zakresy
+fn compress(symbols: &[char]) -> Vec<String> {
+    let mut written = Vec::new();
+    let mut i = 0;
+    while i < symbols.len() {
+        let mut j = i;
+        while j + 1 < symbols.len()
+            && symbols[j + 1] as u32 == symbols[j] as u32 + 1
+            && symbols[j + 1].is_ascii_alphanumeric()
+            && same_class(symbols[i], symbols[j + 1])
+        {
+            j += 1;
+        }
+        if j >= i + 2 && symbols[i].is_ascii_alphanumeric() {
+            written.push(format!("{}-{}", symbols[i], symbols[j]));
+            i = j + 1;
+        } else {
+            written.push(quote_symbol(symbols[i]));
+            i += 1;
+        }
+    }
+    written
+}
+
+fn same_class(a: char, b: char) -> bool {
+    (a.is_ascii_digit() && b.is_ascii_digit())
+        || (a.is_ascii_lowercase() && b.is_ascii_lowercase())
+        || (a.is_ascii_uppercase() && b.is_ascii_uppercase())
+}
+
+fn quote_symbol(c: char) -> String {
+    // Znaki, które bez apostrofów zostałyby odczytane inaczej
+    let special = c.is_whitespace() || SPECIAL.contains(&c) || matches!(c, '[' | '\\');
+    match c {
+        '\'' | '\\' => format!("'\\{}'", c),
+        '\n' => "'\\n'".to_string(),
+        '\t' => "'\\t'".to_string(),
+        c if special => format!("'{}'", c),
+        c => c.to_string(),
+    }
+}
+
+fn quote_name(name: &str) -> String {
+    let plain = !name.is_empty()
+        && !KEYWORDS.contains(&name)
+        && !name.chars().any(|c| c.is_whitespace() || SPECIAL.contains(&c));
+    if plain {
+        return name.to_string();
+    }
+    let mut quoted = String::from("\"");
+    for c in name.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(text: &str) {
+        let written = write(&parse(text).unwrap().to_enas());
+        assert_eq!(write(&parse(&written).unwrap().to_enas()), written);
+    }
+
+    #[test]
+    fn write_read_round_trip() {
+        round_trip("alphabet a-c 0 ' ' ';'\nstart q0\naccept q2\nq0 -a,b-> q1\nq1 -ε-> q2, q0\nq2 -' ',';'-> \"q 3\"");
+    }
+
+    #[test]
+    fn empty_automaton_round_trip() {
+        let written = write(&ENAS::new(Alphabet::new()));
+        assert!(!written.contains("start"));
+        let enas = parse(&written).unwrap().to_enas();
+        assert!(enas.get_states().is_empty());
+        assert_eq!(enas.get_start_state(), "");
+    }
+
+    #[test]
+    fn format_to_canonical_form() {
+        let text = "q1 -b-> q0 # komentarz\nstart q0; accept q1\nq0 -a-> q1; q0 -c-> q1";
+        assert_eq!(format(text).unwrap(), "alphabet a-c\nstart q0\naccept q1\nq0 -a,c-> q1\nq1 -b-> q0\n");
+    }
+
+    #[test]
+    fn symbols_outside_declared_alphabet() {
+        let error = parse("alphabet a\nstart q0\nq0 -b-> q0").err().unwrap();
+        assert_eq!((error.line, error.column), (3, 5));
+        assert_eq!(error.message, "Symbol 'b' nie należy do zadeklarowanego alfabetu.");
+    }
+
+    #[test]
+    fn missing_start_is_an_error() {
+        let error = parse("accept q0\nq0 -a-> q0").err().unwrap();
+        assert_eq!(error.message, "Brak instrukcji 'start <stan>'.");
+    }
+
+    #[test]
+    fn error_position() {
+        let error = parse("start q0\nq0 -a q1").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 7));
+        assert_eq!(error.message, "Oczekiwano ',' albo '->'.");
+    }
+}
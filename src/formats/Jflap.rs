@@ -0,0 +1,219 @@
+use crate::automats::ENAS::ENAS;
+use crate::elements::Alphabet::Alphabet;
+use crate::elements::Label::Label;
+use crate::elements::Node::{ENASNode, NodeBase};
+use crate::elements::Symbol::EPSILON;
+use crate::formats::{ordered_states, state_positions, xml_escape, Automat};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+
+struct JflapState {
+    name: String,
+    position: Option<(f32, f32)>,
+    initial: bool,
+    accepting: bool,
+}
+
+// Wczytuje automat skończony (typ "fa") z pliku JFLAP. Puste <read/> to
+// przejście λ (ε); współrzędne węzłów trafiają do położeń stanów.
+pub fn read(xml: &str) -> Result<Automat, String> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| format!("Niepoprawny XML: {}", e))?;
+    let structure = document.root_element();
+    if !structure.has_tag_name("structure") {
+        return Err(format!("Oczekiwano elementu <structure>, jest <{}>.", structure.tag_name().name()));
+    }
+    let kind = child_text(structure, "type").map_or("fa", str::trim);
+    if kind != "fa" {
+        return Err(format!("Nieobsługiwany typ automatu JFLAP '{}' – obsługiwane są tylko automaty skończone (fa).", kind));
+    }
+    // JFLAP 4 zapisuje stany bezpośrednio w <structure>, nowsze wersje w <automaton>
+    let automaton = structure.children().find(|n| n.has_tag_name("automaton")).unwrap_or(structure);
+
+    let mut states: BTreeMap<String, JflapState> = BTreeMap::new();
+    let mut names: HashSet<String> = HashSet::new();
+    for node in automaton.children().filter(|n| n.has_tag_name("state")) {
+        let id = node.attribute("id").ok_or("Stan bez atrybutu id.")?.to_string();
+        let base = node.attribute("name").map_or_else(|| format!("q{}", id), str::to_string);
+        // Nazwy w JFLAP nie muszą być unikalne – powtórzenia dostają id jako
+        // przyrostek, a gdy i taka nazwa jest zajęta, także kolejny numer
+        let mut name = base.clone();
+        let mut suffix = 1;
+        while !names.insert(name.clone()) {
+            name = if suffix == 1 { format!("{}_{}", base, id) } else { format!("{}_{}_{}", base, id, suffix) };
+            suffix += 1;
+        }
+        let coordinate = |tag| child_text(node, tag).and_then(|t| t.trim().parse::<f32>().ok());
+        let position = coordinate("x").zip(coordinate("y"));
+        let state = JflapState {
+            name,
+            position,
+            initial: node.children().any(|n| n.has_tag_name("initial")),
+            accepting: node.children().any(|n| n.has_tag_name("final")),
+        };
+        if states.insert(id.clone(), state).is_some() {
+            return Err(format!("Powtórzony identyfikator stanu {}.", id));
+        }
+    }
+
+    let mut alphabet = Alphabet::new();
+    let mut connections: BTreeMap<(String, Label), Vec<String>> = BTreeMap::new();
+    for node in automaton.children().filter(|n| n.has_tag_name("transition")) {
+        let state = |tag| {
+            let id = child_text(node, tag).map(str::trim).unwrap_or_default();
+            states
+                .get(id)
+                .map(|s| s.name.clone())
+                .ok_or_else(|| format!("Przejście odwołuje się do nieistniejącego stanu '{}'.", id))
+        };
+        let (from, to) = (state("from")?, state("to")?);
+        let read = child_text(node, "read").unwrap_or_default();
+        let mut chars = read.chars();
+        let label = match (chars.next(), chars.next()) {
+            (None, _) => Label::Epsilon,
+            _ if read == EPSILON => Label::Epsilon,
+            (Some(c), None) => {
+                alphabet.add(c);
+                Label::Symbol(c)
+            }
+            _ => {
+                return Err(format!(
+                    "Przejście {} → {} czyta wieloznakowy napis '{}' – obsługiwane są tylko pojedyncze znaki.",
+                    from, to, read
+                ))
+            }
+        };
+        let targets = connections.entry((from, label)).or_default();
+        if !targets.contains(&to) {
+            targets.push(to);
+        }
+    }
+
+    let mut enas = ENAS::new(alphabet);
+    let mut initial = states.values().filter(|s| s.initial);
+    match (initial.next(), initial.next()) {
+        (Some(start), None) => enas.set_start_state(&start.name),
+        (None, _) => return Err("Automat nie ma stanu początkowego.".to_string()),
+        (Some(_), Some(_)) => return Err("Automat ma więcej niż jeden stan początkowy.".to_string()),
+    }
+    let mut nodes: HashMap<String, ENASNode> = states
+        .values()
+        .map(|s| {
+            let mut node = ENASNode::new(&s.name, s.accepting);
+            node.set_position(s.position);
+            (s.name.clone(), node)
+        })
+        .collect();
+    for ((from, label), targets) in connections {
+        nodes.get_mut(&from).unwrap().add_connection(label, targets);
+    }
+    for node in nodes.into_values() {
+        enas.add_state(node);
+    }
+    Ok(Automat::from_enas(enas))
+}
+
+// Zapis w formacie JFLAP 7. Stany bez położenia dostają układ warstwowy.
+// JFLAP nie przechowuje alfabetu, więc symbole bez przejść nie są zapisywane.
+pub fn write(enas: &ENAS) -> String {
+    let order = ordered_states(enas.get_start_state(), enas.get_states().keys());
+    let ids: HashMap<&str, usize> = order.iter().enumerate().map(|(i, s)| (s.as_str(), i)).collect();
+    let positions = state_positions(enas, &order);
+
+    let mut xml = String::new();
+    writeln!(xml, "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?><!--Utworzono w rust-state-machine.-->").unwrap();
+    writeln!(xml, "<structure>").unwrap();
+    writeln!(xml, "\t<type>fa</type>").unwrap();
+    writeln!(xml, "\t<automaton>").unwrap();
+    writeln!(xml, "\t\t<!--The list of states.-->").unwrap();
+    for (i, name) in order.iter().enumerate() {
+        let node = &enas.get_states()[name];
+        let (x, y) = positions[i];
+        writeln!(xml, "\t\t<state id=\"{}\" name=\"{}\">", i, xml_escape(name)).unwrap();
+        writeln!(xml, "\t\t\t<x>{:.1}</x>", x).unwrap();
+        writeln!(xml, "\t\t\t<y>{:.1}</y>", y).unwrap();
+        if name == enas.get_start_state() {
+            writeln!(xml, "\t\t\t<initial/>").unwrap();
+        }
+        if node.is_accepting() {
+            writeln!(xml, "\t\t\t<final/>").unwrap();
+        }
+        writeln!(xml, "\t\t</state>").unwrap();
+    }
+    writeln!(xml, "\t\t<!--The list of transitions.-->").unwrap();
+    for name in &order {
+        let mut connections: Vec<_> = enas.get_states()[name].get_connections().iter().collect();
+        connections.sort();
+        for (label, targets) in connections {
+            // Przejścia do nieistniejących stanów nie mają odpowiednika w JFLAP
+            for target in targets.iter().filter(|t| ids.contains_key(t.as_str())) {
+                writeln!(xml, "\t\t<transition>").unwrap();
+                writeln!(xml, "\t\t\t<from>{}</from>", ids[name.as_str()]).unwrap();
+                writeln!(xml, "\t\t\t<to>{}</to>", ids[target.as_str()]).unwrap();
+                match label {
+                    Label::Symbol(c) => writeln!(xml, "\t\t\t<read>{}</read>", xml_escape(&c.to_string())).unwrap(),
+                    Label::Epsilon => writeln!(xml, "\t\t\t<read/>").unwrap(),
+                }
+                writeln!(xml, "\t\t</transition>").unwrap();
+            }
+        }
+    }
+    writeln!(xml, "\t</automaton>").unwrap();
+    writeln!(xml, "</structure>").unwrap();
+    xml
+}
+
+fn child_text<'a>(node: roxmltree::Node<'a, '_>, tag: &str) -> Option<&'a str> {
+    node.children().find(|n| n.has_tag_name(tag)).map(|n| n.text().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::Dsl;
+
+    #[test]
+    fn write_read_round_trip() {
+        let mut enas = Dsl::parse("start q0\naccept q2\nq0 -a,<-> q1\nq1 -ε-> q2, q0\nq2 -'&'-> \"q 3\"")
+            .unwrap()
+            .to_enas();
+        let mut node = enas.get_states()["q1"].clone();
+        node.set_position(Some((120.0, 40.0)));
+        enas.add_state(node);
+
+        let read = read(&write(&enas)).unwrap().to_enas();
+        assert_eq!(Dsl::write(&read), Dsl::write(&enas));
+        assert_eq!(read.get_states()["q1"].get_position(), Some((120.0, 40.0)));
+    }
+
+    #[test]
+    fn lambda_and_jflap4_layout() {
+        let xml = r#"<structure><type>fa</type>
+            <state id="0"><initial/></state>
+            <state id="1"><final/></state>
+            <transition><from>0</from><to>1</to><read/></transition>
+        </structure>"#;
+        let enas = read(xml).unwrap().to_enas();
+        assert_eq!(enas.get_start_state(), "q0");
+        assert!(enas.accepts("".chars()));
+    }
+
+    #[test]
+    fn rejects_other_machines() {
+        assert!(read("<structure><type>turing</type></structure>").is_err());
+        assert!(read("<automaton/>").is_err());
+    }
+
+    #[test]
+    fn duplicate_names_become_unique() {
+        let xml = r#"<structure><type>fa</type><automaton>
+            <state id="0" name="q"><initial/></state>
+            <state id="1" name="q"/>
+            <state id="2" name="q_1"/>
+            <state id="3" name="q_1"/>
+        </automaton></structure>"#;
+        let enas = read(xml).unwrap().to_enas();
+        let mut names: Vec<&String> = enas.get_states().keys().collect();
+        names.sort();
+        assert_eq!(names, ["q", "q_1", "q_1_2", "q_1_3"]);
+    }
+}
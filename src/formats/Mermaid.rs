@@ -0,0 +1,92 @@
+use crate::automats::ENAS::ENAS;
+use crate::elements::Node::NodeBase;
+use crate::formats::{diagram_label, edges, ordered_states};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+// Diagram `stateDiagram-v2` dla Mermaid. Stany mają identyfikatory s0, s1, …
+// i nazwy jako opisy, więc dowolna nazwa stanu jest bezpieczna; stany
+// akceptujące dostają notatkę i pogrubioną ramkę.
+pub fn write(enas: &ENAS) -> String {
+    let order = ordered_states(enas.get_start_state(), enas.get_states().keys());
+    let ids: HashMap<&str, String> = order.iter().enumerate().map(|(i, s)| (s.as_str(), format!("s{}", i))).collect();
+
+    let mut text = String::new();
+    writeln!(text, "stateDiagram-v2").unwrap();
+    writeln!(text, "    direction LR").unwrap();
+    writeln!(text, "    classDef accepting stroke-width:3px").unwrap();
+    for name in &order {
+        writeln!(text, "    state \"{}\" as {}", escape(name), ids[name.as_str()]).unwrap();
+    }
+    if let Some(start) = ids.get(enas.get_start_state()) {
+        writeln!(text, "    [*] --> {}", start).unwrap();
+    }
+    for edge in edges(enas, &order) {
+        writeln!(
+            text,
+            "    {} --> {} : {}",
+            ids[edge.from.as_str()],
+            ids[edge.to.as_str()],
+            escape(&diagram_label(&edge.labels))
+        )
+        .unwrap();
+    }
+    for name in order.iter().filter(|s| enas.get_states()[*s].is_accepting()) {
+        let id = &ids[name.as_str()];
+        writeln!(text, "    note right of {} : stan akceptujący", id).unwrap();
+        writeln!(text, "    class {} accepting", id).unwrap();
+    }
+    text
+}
+
+// Znaki składni Mermaid i końce wierszy jako kody encji (#35; itd.)
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if matches!(c, '#' | ';' | '"' | ':' | '<' | '>' | '&' | '{' | '}' | '%' | '|' | '\n' | '\r') {
+            write!(escaped, "#{};", c as u32).unwrap();
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::Alphabet::Alphabet;
+    use crate::elements::Label::Label;
+    use crate::elements::Node::ENASNode;
+
+    // Dwa stany o podanych nazwach połączone przejściem po `symbol`
+    fn automat(from: &str, to: &str, symbol: char) -> ENAS {
+        let mut alphabet = Alphabet::new();
+        alphabet.add(symbol);
+        let mut enas = ENAS::new(alphabet);
+        let mut start = ENASNode::new(from, false);
+        start.add_connection(Label::Symbol(symbol), vec![to.to_string()]);
+        enas.add_state(start);
+        enas.add_state(ENASNode::new(to, true));
+        enas.set_start_state(from);
+        enas
+    }
+
+    #[test]
+    fn special_characters_are_escaped() {
+        assert_eq!(escape("a\"b"), "a#34;b");
+        assert_eq!(escape("x: y"), "x#58; y");
+        assert_eq!(escape("#1"), "#35;1");
+        assert_eq!(escape("**q**"), "**q**");
+        assert_eq!(escape("a\nb\r"), "a#10;b#13;");
+    }
+
+    #[test]
+    fn names_do_not_break_lines() {
+        let text = write(&automat("start\n\"q\": #1", "**koniec**", ':'));
+        assert_eq!(text.lines().count(), 9);
+        assert!(text.contains("    state \"start#10;#34;q#34;#58; #35;1\" as s0\n"));
+        assert!(text.contains("    state \"**koniec**\" as s1\n"));
+        assert!(text.contains("    s0 --> s1 : #58;\n"));
+    }
+}
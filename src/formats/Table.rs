@@ -0,0 +1,380 @@
+use crate::automats::ENAS::ENAS;
+use crate::elements::Alphabet::Alphabet;
+use crate::elements::Label::Label;
+use crate::elements::Node::ENASNode;
+use crate::elements::Symbol::EPSILON;
+use crate::formats::{latex_escape, Automat};
+use std::fmt::Write;
+
+// Tabela przejść w postaci arkusza: nagłówek z symbolami (kolumna ε, jeśli
+// jest, zawsze ostatnia) i wiersz na stan. Komórki to tekst jak w tabeli GUI –
+// nazwa stanu docelowego albo lista stanów oddzielonych przecinkami.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransitionTable {
+    pub symbols: Vec<String>,
+    pub epsilon: bool,
+    pub rows: Vec<TableRow>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableRow {
+    pub state: String,
+    pub start: bool,
+    pub accepting: bool,
+    // Komórki dla kolejnych symboli i na końcu dla ε; pusta – brak przejścia
+    pub cells: Vec<String>,
+}
+
+// Znaczniki w kolumnie stanów: "->q0" (startowy), "*q1" (akceptujący), "->*q2"
+const START_MARKERS: [&str; 2] = ["->", "→"];
+const ACCEPTING_MARKER: &str = "*";
+const EPSILON_HEADERS: [&str; 4] = [EPSILON, "λ", "eps", "epsilon"];
+const EMPTY_CELLS: [&str; 2] = ["-", "∅"];
+
+impl TransitionTable {
+    // CSV (separator ',' lub ';') albo TSV (separator '\t'), np. blok skopiowany
+    // z arkusza. Pola w cudzysłowach mogą zawierać separator i znaki nowej linii.
+    pub fn parse(text: &str, delimiter: char) -> Result<TransitionTable, String> {
+        let mut records = parse_records(text, delimiter)?.into_iter();
+        let header = records.next().ok_or("Pusta tabela.")?;
+        let mut symbols: Vec<String> = header.into_iter().skip(1).collect();
+        while symbols.last().is_some_and(|s| s.is_empty()) {
+            symbols.pop();
+        }
+        if let Some(i) = symbols.iter().position(|s| s.is_empty()) {
+            return Err(format!("Pusty nagłówek kolumny {}.", i + 2));
+        }
+        // Kolumna ε przenoszona na koniec, tak jak w tabeli GUI
+        let epsilon_column = symbols
+            .iter()
+            .position(|s| EPSILON_HEADERS.iter().any(|e| s.eq_ignore_ascii_case(e)));
+        if let Some(i) = epsilon_column {
+            symbols.remove(i);
+        }
+
+        let mut rows = Vec::new();
+        for (line, record) in records.enumerate() {
+            let mut cells = record.into_iter();
+            let marked = cells.next().unwrap_or_default();
+            let mut cells: Vec<String> = cells
+                .map(|c| if EMPTY_CELLS.contains(&c.as_str()) { String::new() } else { c })
+                .collect();
+            let width = symbols.len() + usize::from(epsilon_column.is_some());
+            while cells.len() > width && cells.last().is_some_and(|c| c.is_empty()) {
+                cells.pop();
+            }
+            if cells.len() > width {
+                return Err(format!("Wiersz {} ma {} komórek przejść, a nagłówek {}.", line + 2, cells.len(), width));
+            }
+            cells.resize(width, String::new());
+            if let Some(i) = epsilon_column {
+                let epsilon = cells.remove(i);
+                cells.push(epsilon);
+            }
+            let (state, start, accepting) = parse_markers(&marked);
+            if state.is_empty() {
+                return Err(format!("Wiersz {} nie ma nazwy stanu.", line + 2));
+            }
+            rows.push(TableRow { state, start, accepting, cells });
+        }
+
+        let starts = rows.iter().filter(|r| r.start).count();
+        if starts > 1 {
+            return Err("Tabela ma więcej niż jeden stan startowy (->).".to_string());
+        }
+        // Bez znacznika startowy jest pierwszy wiersz
+        if starts == 0 && let Some(first) = rows.first_mut() {
+            first.start = true;
+        }
+        Ok(TransitionTable { symbols, epsilon: epsilon_column.is_some(), rows })
+    }
+
+    // Automat na znakach, jak tabela GUI w trybie znaków: nagłówki to znaki,
+    // zakresy albo klasy, a komórka to nazwa stanu lub lista oddzielona przecinkami
+    pub fn to_automat(&self) -> Result<Automat, String> {
+        let columns = self
+            .symbols
+            .iter()
+            .map(|s| Alphabet::expand(s))
+            .collect::<Result<Vec<Vec<char>>, String>>()?;
+        let mut alphabet = Alphabet::new();
+        for symbol in columns.iter().flatten() {
+            alphabet.add(*symbol);
+        }
+        let mut enas = ENAS::new(alphabet);
+        for (line, row) in self.rows.iter().enumerate() {
+            let mut node = ENASNode::new(&row.state, row.accepting);
+            for (j, cell) in row.cells.iter().enumerate() {
+                let targets = self.targets(cell, line)?;
+                if targets.is_empty() {
+                    continue;
+                }
+                match columns.get(j) {
+                    Some(symbols) => {
+                        for symbol in symbols {
+                            node.add_connection(Label::Symbol(*symbol), targets.clone());
+                        }
+                    }
+                    None => node.add_connection(Label::Epsilon, targets),
+                }
+            }
+            if row.start {
+                enas.set_start_state(&row.state);
+            }
+            enas.add_state(node);
+        }
+        Ok(Automat::from_enas(enas))
+    }
+
+    // Stany docelowe komórki; nazwa istniejącego stanu z przecinkiem nie jest dzielona
+    fn targets(&self, cell: &str, line: usize) -> Result<Vec<String>, String> {
+        let is_state = |name: &str| self.rows.iter().any(|r| r.state == name);
+        let targets: Vec<String> = if is_state(cell) {
+            vec![cell.to_string()]
+        } else {
+            cell.split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect()
+        };
+        match targets.iter().find(|t| !is_state(t)) {
+            Some(unknown) => Err(format!("Wiersz {}: nieznany stan docelowy '{}'.", line + 2, unknown)),
+            None => Ok(targets),
+        }
+    }
+
+    pub fn to_delimited(&self, delimiter: char) -> String {
+        let mut text = String::new();
+        for record in self.records() {
+            let fields: Vec<String> = record.iter().map(|f| quote(f, delimiter)).collect();
+            writeln!(text, "{}", fields.join(&delimiter.to_string())).unwrap();
+        }
+        text
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut header = vec![String::new()];
+        header.extend(self.symbols.iter().map(|s| markdown_escape(s)));
+        if self.epsilon {
+            header.push(EPSILON.to_string());
+        }
+        let mut text = String::new();
+        writeln!(text, "| {} |", header.join(" | ")).unwrap();
+        writeln!(text, "|{}", "---|".repeat(header.len())).unwrap();
+        for row in &self.rows {
+            let mut state = markdown_escape(&row.state);
+            if row.accepting {
+                state = format!("\\* {}", state);
+            }
+            if row.start {
+                state = format!("→ {}", state);
+            }
+            let mut cells = vec![state];
+            cells.extend(row.cells.iter().map(|c| if c.is_empty() { "∅".to_string() } else { markdown_escape(c) }));
+            writeln!(text, "| {} |", cells.join(" | ")).unwrap();
+        }
+        text
+    }
+
+    pub fn to_latex(&self) -> String {
+        let columns = self.symbols.len() + usize::from(self.epsilon);
+        let mut text = String::new();
+        writeln!(text, "\\begin{{tabular}}{{l|{}}}", "c".repeat(columns)).unwrap();
+        let mut header = vec![String::new()];
+        header.extend(self.symbols.iter().map(|s| latex_escape(s)));
+        if self.epsilon {
+            header.push("$\\varepsilon$".to_string());
+        }
+        writeln!(text, "  {} \\\\", header.join(" & ")).unwrap();
+        writeln!(text, "  \\hline").unwrap();
+        for row in &self.rows {
+            let mut state = latex_escape(&row.state);
+            if row.accepting {
+                state = format!("$*$ {}", state);
+            }
+            if row.start {
+                state = format!("$\\to$ {}", state);
+            }
+            let mut cells = vec![state];
+            cells.extend(row.cells.iter().map(|c| {
+                if c.is_empty() { "$\\emptyset$".to_string() } else { latex_escape(c) }
+            }));
+            writeln!(text, "  {} \\\\", cells.join(" & ")).unwrap();
+        }
+        writeln!(text, "\\end{{tabular}}").unwrap();
+        text
+    }
+
+    // Nagłówek i wiersze jako pola CSV ze znacznikami stanów
+    fn records(&self) -> Vec<Vec<String>> {
+        let mut header = vec![String::new()];
+        header.extend(self.symbols.iter().cloned());
+        if self.epsilon {
+            header.push(EPSILON.to_string());
+        }
+        let mut records = vec![header];
+        for row in &self.rows {
+            let marked = format!(
+                "{}{}{}",
+                if row.start { START_MARKERS[0] } else { "" },
+                if row.accepting { ACCEPTING_MARKER } else { "" },
+                row.state
+            );
+            let mut record = vec![marked];
+            record.extend(row.cells.iter().cloned());
+            records.push(record);
+        }
+        records
+    }
+}
+
+// Znaczniki "->" i "*" w dowolnej kolejności przed nazwą stanu
+fn parse_markers(cell: &str) -> (String, bool, bool) {
+    let (mut start, mut accepting) = (false, false);
+    let mut rest = cell.trim();
+    loop {
+        if let Some(marker) = START_MARKERS.iter().find(|m| rest.starts_with(*m)) {
+            start = true;
+            rest = rest[marker.len()..].trim_start();
+        } else if let Some(stripped) = rest.strip_prefix(ACCEPTING_MARKER) {
+            accepting = true;
+            rest = stripped.trim_start();
+        } else {
+            return (rest.to_string(), start, accepting);
+        }
+    }
+}
+
+// Rekordy CSV z przyciętymi polami; puste wiersze są pomijane
+fn parse_records(text: &str, delimiter: char) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            '\n' if !quoted => {
+                record.push(field.trim().to_string());
+                field.clear();
+                if record.iter().any(|f| !f.is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+                line += 1;
+            }
+            c if c == delimiter && !quoted => {
+                record.push(field.trim().to_string());
+                field.clear();
+            }
+            '\r' if !quoted => {}
+            c => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    if quoted {
+        return Err(format!("Niezamknięty cudzysłów (wiersz {}).", line));
+    }
+    record.push(field.trim().to_string());
+    if record.iter().any(|f| !f.is_empty()) {
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn quote(field: &str, delimiter: char) -> String {
+    if field.contains(delimiter) || field.contains(['"', '\n', '\r']) || field.trim() != field {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn markdown_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if matches!(c, '\\' | '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> TransitionTable {
+        let row = |state: &str, start, accepting, cells: &[&str]| TableRow {
+            state: state.to_string(),
+            start,
+            accepting,
+            cells: cells.iter().map(|c| c.to_string()).collect(),
+        };
+        TransitionTable {
+            symbols: vec!["a".to_string(), ",".to_string(), "\"".to_string()],
+            epsilon: true,
+            rows: vec![
+                row("q0", true, false, &["q1", "q 2", "", "q1,q0"]),
+                row("q1", false, true, &["", "q0", "q1", ""]),
+                row("q 2", false, true, &["q0", "", "", ""]),
+            ],
+        }
+    }
+
+    #[test]
+    fn write_read_round_trip() {
+        let table = table();
+        for delimiter in [',', ';', '\t'] {
+            assert_eq!(TransitionTable::parse(&table.to_delimited(delimiter), delimiter).unwrap(), table);
+        }
+    }
+
+    #[test]
+    fn markers_empty_cells_and_epsilon_column() {
+        let table = TransitionTable::parse("stan;eps;b\n*q1;-;∅\n→*q0;q1;q0;;\n", ';').unwrap();
+        assert_eq!(table.symbols, ["b"]);
+        assert!(table.epsilon);
+        assert_eq!(table.rows[1].state, "q0");
+        assert!(table.rows[1].start && table.rows[1].accepting && !table.rows[0].start);
+        assert_eq!(table.rows[1].cells, ["q0", "q1"]);
+        assert_eq!(table.rows[0].cells, ["", ""]);
+    }
+
+    #[test]
+    fn rejects_malformed_tables() {
+        assert!(TransitionTable::parse("", ',').is_err());
+        assert!(TransitionTable::parse(",a\nq0,q0,q1\n", ',').is_err());
+        assert!(TransitionTable::parse(",a\n->q0,q0\n->q1,q0\n", ',').is_err());
+        assert!(TransitionTable::parse(",a\n\"q0,q0\n", ',').is_err());
+    }
+
+    #[test]
+    fn table_to_automat() {
+        let table = TransitionTable::parse(",0-1,ε\n->q0,q1,\n*q1,q1,q0\n", ',').unwrap();
+        let enas = table.to_automat().unwrap().to_enas();
+        assert!(enas.accepts("0101".chars()));
+        assert!(!enas.accepts("".chars()));
+    }
+
+    #[test]
+    fn unknown_target_is_an_error() {
+        let table = TransitionTable::parse(",a\nq0,q9\n", ',').unwrap();
+        assert!(table.to_automat().is_err());
+    }
+}
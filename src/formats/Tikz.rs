@@ -0,0 +1,120 @@
+use crate::automats::ENAS::ENAS;
+use crate::elements::Label::Label;
+use crate::elements::Node::NodeBase;
+use crate::formats::{edges, latex_escape, ordered_states, state_positions};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+// Piksele układu (jak w JFLAP) na centymetry
+const SCALE: f32 = 60.0;
+
+// Środowisko `tikzpicture` dla biblioteki `automata`. Położenia stanów pochodzą
+// z układu diagramu; krawędzie w obie strony są wygięte, pętle skierowane
+// w górę, a przejścia między tą samą parą stanów łączone w jedną etykietę.
+pub fn write(enas: &ENAS) -> String {
+    let order = ordered_states(enas.get_start_state(), enas.get_states().keys());
+    let ids: HashMap<&str, String> = order.iter().enumerate().map(|(i, s)| (s.as_str(), format!("s{}", i))).collect();
+    let positions = state_positions(enas, &order);
+
+    let mut tex = String::new();
+    writeln!(tex, "% Wymaga: \\usetikzlibrary{{automata, arrows.meta}}").unwrap();
+    writeln!(
+        tex,
+        "\\begin{{tikzpicture}}[shorten >=1pt, auto, >={{Stealth[round]}}, initial text={{}}]"
+    )
+    .unwrap();
+    for (i, name) in order.iter().enumerate() {
+        let node = &enas.get_states()[name];
+        let mut style = vec!["state"];
+        if name == enas.get_start_state() {
+            style.push("initial");
+        }
+        if node.is_accepting() {
+            style.push("accepting");
+        }
+        let (x, y) = positions[i];
+        writeln!(
+            tex,
+            "  \\node[{}] ({}) at ({:.2}, {:.2}) {{{}}};",
+            style.join(", "),
+            ids[name.as_str()],
+            x / SCALE,
+            -y / SCALE,
+            state_label(name)
+        )
+        .unwrap();
+    }
+
+    let edges = edges(enas, &order);
+    if !edges.is_empty() {
+        let pairs: HashSet<(&str, &str)> = edges.iter().map(|e| (e.from.as_str(), e.to.as_str())).collect();
+        writeln!(tex, "  \\path[->]").unwrap();
+        let lines: Vec<String> = edges
+            .iter()
+            .map(|edge| {
+                let label = edge.labels.iter().map(transition_label).collect::<Vec<_>>().join(", ");
+                let (from, to) = (&ids[edge.from.as_str()], &ids[edge.to.as_str()]);
+                if edge.from == edge.to {
+                    format!("    ({}) edge [loop above] node {{{}}} ()", from, label)
+                } else if pairs.contains(&(edge.to.as_str(), edge.from.as_str())) {
+                    format!("    ({}) edge [bend left] node {{{}}} ({})", from, label, to)
+                } else {
+                    format!("    ({}) edge node {{{}}} ({})", from, label, to)
+                }
+            })
+            .collect();
+        writeln!(tex, "{};", lines.join("\n")).unwrap();
+    }
+    writeln!(tex, "\\end{{tikzpicture}}").unwrap();
+    tex
+}
+
+// "q0" → $q_{0}$, pozostałe nazwy jako zwykły tekst
+fn state_label(name: &str) -> String {
+    let digits = name.len() - name.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    let (letters, index) = name.split_at(name.len() - digits);
+    if digits > 0 && !letters.is_empty() && letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        format!("${}_{{{}}}$", letters, index)
+    } else {
+        latex_escape(name)
+    }
+}
+
+// Przecinek oddziela symbole w etykiecie, więc jako symbol jest ujęty
+// w cudzysłów; spacja jako symbol byłaby niewidoczna
+fn transition_label(label: &Label) -> String {
+    match label {
+        Label::Symbol(',') => "`{,}'".to_string(),
+        Label::Symbol(' ') => "\\textvisiblespace{}".to_string(),
+        Label::Symbol(c) => latex_escape(&c.to_string()),
+        Label::Epsilon => "$\\varepsilon$".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::Dsl;
+
+    #[test]
+    fn snapshot() {
+        // Stan startowy i akceptujący, pętla, krawędzie w obie strony i etykieta
+        // z kilku symboli, w tym przecinka i spacji
+        let enas = Dsl::parse("start q0\naccept q1\nq0 -a,',',' '-> q1\nq1 -b-> q1\nq1 -ε-> q0\nq0 -_-> x")
+            .unwrap()
+            .to_enas();
+        let expected = r"% Wymaga: \usetikzlibrary{automata, arrows.meta}
+\begin{tikzpicture}[shorten >=1pt, auto, >={Stealth[round]}, initial text={}]
+  \node[state, initial] (s0) at (1.33, -2.25) {$q_{0}$};
+  \node[state, accepting] (s1) at (4.00, -1.33) {$q_{1}$};
+  \node[state] (s2) at (4.00, -3.17) {x};
+  \path[->]
+    (s0) edge [bend left] node {\textvisiblespace{}, `{,}', a} (s1)
+    (s0) edge node {\_} (s2)
+    (s1) edge [bend left] node {$\varepsilon$} (s0)
+    (s1) edge [loop above] node {b} ();
+\end{tikzpicture}
+";
+        assert_eq!(write(&enas), expected);
+    }
+}
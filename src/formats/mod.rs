@@ -0,0 +1,129 @@
+pub mod Att;
+pub mod Dsl;
+pub mod Jflap;
+pub mod Mermaid;
+pub mod PlantUml;
+pub mod Table;
+pub mod Tikz;
+
+use crate::automats::DAS::DAS;
+use crate::automats::ENAS::ENAS;
+use crate::diagram::Layout::{self, LayoutKind};
+use crate::elements::Label::Label;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+// Automat wczytany z pliku: DAS, gdy przejścia są deterministyczne,
+// w przeciwnym razie ε-NAS
+pub enum Automat {
+    DAS(DAS),
+    ENAS(ENAS),
+}
+
+impl Automat {
+    pub fn from_enas(enas: ENAS) -> Automat {
+        match enas.to_das() {
+            Some(das) => Automat::DAS(das),
+            None => Automat::ENAS(enas),
+        }
+    }
+
+    // Zapis do formatów obsługujących ε-NAS – DAS jest jego szczególnym przypadkiem
+    pub fn to_enas(&self) -> ENAS {
+        match self {
+            Automat::DAS(das) => das.to_enas(),
+            Automat::ENAS(enas) => enas.clone(),
+        }
+    }
+}
+
+// Kolejność stanów w plikach i w tabeli: startowy pierwszy, reszta w porządku
+// naturalnym (q2 przed q10)
+pub fn ordered_states<'a>(start: &str, names: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+    let mut states: Vec<String> = names.into_iter().cloned().collect();
+    states.sort_by(|a, b| (a != start).cmp(&(b != start)).then_with(|| natural_cmp(a, b)));
+    states
+}
+
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let split = |s: &str| {
+        let digits = s.len() - s.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        let (prefix, number) = s.split_at(s.len() - digits);
+        (prefix.to_string(), number.parse::<u64>().ok())
+    };
+    split(a).cmp(&split(b)).then_with(|| a.cmp(b))
+}
+
+// Krawędź diagramu: wszystkie przejścia między parą stanów pod jedną etykietą
+pub(crate) struct Edge {
+    pub(crate) from: String,
+    pub(crate) to: String,
+    pub(crate) labels: Vec<Label>,
+}
+
+// Krawędzie uporządkowane według pozycji stanów w `order` (bez przejść do
+// nieistniejących stanów)
+pub(crate) fn edges(enas: &ENAS, order: &[String]) -> Vec<Edge> {
+    let index: HashMap<&str, usize> = order.iter().enumerate().map(|(i, s)| (s.as_str(), i)).collect();
+    let mut grouped: BTreeMap<(usize, usize), Vec<Label>> = BTreeMap::new();
+    for (from, node) in enas.get_states() {
+        for (label, targets) in node.get_connections() {
+            for to in targets.iter().filter_map(|t| index.get(t.as_str())) {
+                grouped.entry((index[from.as_str()], *to)).or_default().push(*label);
+            }
+        }
+    }
+    grouped
+        .into_iter()
+        .map(|((from, to), mut labels)| {
+            labels.sort();
+            Edge { from: order[from].clone(), to: order[to].clone(), labels }
+        })
+        .collect()
+}
+
+// Położenia stanów w kolejności `order` (współrzędne jak w JFLAP: piksele,
+// oś y w dół); stany bez zapisanego położenia dostają układ warstwowy
+pub(crate) fn state_positions(enas: &ENAS, order: &[String]) -> Vec<(f32, f32)> {
+    Layout::positions(enas, order, LayoutKind::Stored)
+}
+
+// Etykieta krawędzi diagramu: symbole po przecinku, białe znaki widoczne (␣, ↵, ⇥)
+pub(crate) fn diagram_label(labels: &[Label]) -> String {
+    let written: Vec<String> = labels
+        .iter()
+        .map(|label| match label {
+            Label::Symbol(' ') => "␣".to_string(),
+            Label::Symbol('\n') => "↵".to_string(),
+            Label::Symbol('\t') => "⇥".to_string(),
+            label => label.to_string(),
+        })
+        .collect();
+    written.join(", ")
+}
+
+// Tekst bezpieczny w treści i atrybutach XML
+pub(crate) fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Tekst bezpieczny w trybie tekstowym LaTeX-a
+pub(crate) fn latex_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
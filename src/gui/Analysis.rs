@@ -0,0 +1,57 @@
+use std::ops::Range;
+
+use rust_state_machine::automats::Search::MatchKind;
+
+use crate::gui::GridAutomat::GridAutomat;
+
+// (nazwa własności, czy zachodzi, opis świadka) – jak w `GridAutomat`
+type Property = (&'static str, bool, Option<String>);
+
+// Wartość zapamiętana razem z parametrami, dla których ją policzono
+struct Cached<K, V>(Option<(K, V)>);
+
+impl<K: PartialEq, V> Cached<K, V> {
+    fn get(&mut self, key: K, compute: impl FnOnce() -> V) -> &V {
+        if self.0.as_ref().is_none_or(|(k, _)| *k != key) {
+            let value = compute();
+            self.0 = Some((key, value));
+        }
+        &self.0.as_ref().unwrap().1
+    }
+}
+
+impl<K, V> Default for Cached<K, V> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+// Wyniki paneli analizy automatu z tabeli. Liczone są dopiero przy otwarciu
+// panelu i pamiętane do zmiany ustawień panelu; po zmianie tabeli cała
+// struktura jest zastępowana nową.
+#[derive(Default)]
+pub struct Analysis {
+    properties: Cached<(), Vec<Property>>,
+    // Klucz: (liczba słów, maks. długość)
+    samples: Cached<(usize, usize), (Vec<String>, Vec<String>)>,
+    counts: Cached<usize, (Vec<String>, String, String)>,
+    matches: Cached<(String, MatchKind), Vec<Range<usize>>>,
+}
+
+impl Analysis {
+    pub fn properties(&mut self, automat: &dyn GridAutomat) -> &[Property] {
+        self.properties.get((), || automat.language_properties())
+    }
+
+    pub fn samples(&mut self, automat: &dyn GridAutomat, count: usize, max_len: usize) -> &(Vec<String>, Vec<String>) {
+        self.samples.get((count, max_len), || automat.sample_words(count, max_len))
+    }
+
+    pub fn counts(&mut self, automat: &dyn GridAutomat, max_len: usize) -> &(Vec<String>, String, String) {
+        self.counts.get(max_len, || automat.word_counts(max_len))
+    }
+
+    pub fn matches(&mut self, automat: &dyn GridAutomat, text: &str, kind: MatchKind) -> &[Range<usize>] {
+        self.matches.get((text.to_string(), kind), || automat.find_matches(text, kind))
+    }
+}
@@ -0,0 +1,89 @@
+use eframe::egui;
+
+// Wynik eksportu: tekst (podgląd, schowek) albo dane binarne (np. PNG)
+pub enum ExportContent {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+// Okno z wynikiem eksportu: podgląd tekstu, kopiowanie do schowka i zapis do pliku
+pub struct ExportWindow {
+    title: String,
+    content: Result<ExportContent, String>,
+    path: String,
+    status: Option<Result<String, String>>,
+}
+
+impl ExportWindow {
+    pub fn new(title: &str, content: Result<String, String>, file_name: &str) -> Self {
+        Self::with_content(title, content.map(ExportContent::Text), file_name)
+    }
+
+    pub fn binary(title: &str, content: Result<Vec<u8>, String>, file_name: &str) -> Self {
+        Self::with_content(title, content.map(ExportContent::Binary), file_name)
+    }
+
+    fn with_content(title: &str, content: Result<ExportContent, String>, file_name: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            content,
+            path: file_name.to_string(),
+            status: None,
+        }
+    }
+
+    // Zwraca false, gdy użytkownik zamknął okno
+    pub fn show(&mut self, ctx: &egui::Context) -> bool {
+        let mut open = true;
+        egui::Window::new(&self.title)
+            .open(&mut open)
+            .default_size([520.0, 420.0])
+            .show(ctx, |ui| match &self.content {
+                Ok(content) => {
+                    ui.horizontal(|ui| {
+                        if let ExportContent::Text(text) = content
+                            && ui.button("Kopiuj do schowka").clicked()
+                        {
+                            ui.output_mut(|o| o.copied_text = text.clone());
+                            self.status = Some(Ok("Skopiowano do schowka.".to_string()));
+                        }
+                        ui.label("Plik:");
+                        ui.text_edit_singleline(&mut self.path);
+                        if ui.button("Zapisz").clicked() {
+                            let bytes = match content {
+                                ExportContent::Text(text) => text.as_bytes(),
+                                ExportContent::Binary(bytes) => bytes.as_slice(),
+                            };
+                            self.status = Some(match std::fs::write(&self.path, bytes) {
+                                Ok(()) => Ok(format!("Zapisano '{}'.", self.path)),
+                                Err(e) => Err(format!("Nie można zapisać '{}': {}", self.path, e)),
+                            });
+                        }
+                    });
+                    match &self.status {
+                        Some(Ok(message)) => {
+                            ui.colored_label(egui::Color32::GREEN, message);
+                        }
+                        Some(Err(message)) => {
+                            ui.colored_label(egui::Color32::RED, message);
+                        }
+                        None => {}
+                    }
+                    match content {
+                        ExportContent::Text(text) => {
+                            egui::ScrollArea::both().show(ui, |ui| {
+                                ui.add(egui::Label::new(egui::RichText::new(text.as_str()).monospace()).wrap(false));
+                            });
+                        }
+                        ExportContent::Binary(bytes) => {
+                            ui.label(format!("Rozmiar: {} KiB", bytes.len().div_ceil(1024)));
+                        }
+                    }
+                }
+                Err(message) => {
+                    ui.colored_label(egui::Color32::RED, message);
+                }
+            });
+        open
+    }
+}
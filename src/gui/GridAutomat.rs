@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use rust_state_machine::automats::DAS::DAS;
+use rust_state_machine::automats::ENAS::ENAS;
+use rust_state_machine::automats::Search::{Haystack, MatchKind};
+use rust_state_machine::automats::ValidationError::ValidationError;
+use rust_state_machine::automats::Verdict::{format_word, Verdict};
+use rust_state_machine::elements::Alphabet::Alphabet;
+use rust_state_machine::elements::Symbol::{Symbol, Token};
+
+// Symbol, który da się odczytać z tabeli i pola wejściowego GUI
+pub trait GridSymbol: Symbol {
+    // Symbole opisywane przez nagłówek kolumny alfabetu
+    fn parse_column(cell: &str) -> Result<Vec<Self>, String>;
+    // Ciąg wejściowy jako sekwencja symboli
+    fn parse_input(input: &str) -> Vec<Self>;
+}
+
+impl GridSymbol for char {
+    fn parse_column(cell: &str) -> Result<Vec<Self>, String> {
+        Alphabet::expand(cell)
+    }
+
+    fn parse_input(input: &str) -> Vec<Self> {
+        input.chars().collect()
+    }
+}
+
+impl GridSymbol for Token {
+    fn parse_column(cell: &str) -> Result<Vec<Self>, String> {
+        match cell.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [] => Err("Pusty token.".to_string()),
+            [token] => Ok(vec![Token::new(token)]),
+            _ => Err(format!("Token '{}' nie może zawierać spacji.", cell.trim())),
+        }
+    }
+
+    fn parse_input(input: &str) -> Vec<Self> {
+        input.split_whitespace().map(Token::new).collect()
+    }
+}
+
+// Operacje na automacie, z których korzysta GUI – niezależne od rodzaju
+// automatu i typu symboli (symbole i słowa przekazywane są jako tekst)
+pub trait GridAutomat {
+    fn errors(&self, allow_partial: bool) -> Vec<ValidationError<String>>;
+    fn symbols_outside_alphabet(&self, input: &str) -> Vec<String>;
+    fn accepts_input(&self, input: &str) -> bool;
+    fn reachable_states(&self) -> HashSet<String>;
+    fn coreachable_states(&self) -> HashSet<String>;
+    fn trimmed_states(&self) -> HashSet<String>;
+    // (nazwa własności, czy zachodzi, opis świadka)
+    fn language_properties(&self) -> Vec<(&'static str, bool, Option<String>)>;
+    // Pierwsze słowa akceptowane i odrzucane w porządku shortlex
+    fn sample_words(&self, count: usize, max_len: usize) -> (Vec<String>, Vec<String>);
+    // Liczby słów długości 0..=max_len, rekurencja i funkcja tworząca
+    fn word_counts(&self, max_len: usize) -> (Vec<String>, String, String);
+    // Zakresy bajtów kolejnych dopasowań w tekście
+    fn find_matches(&self, text: &str, kind: MatchKind) -> Vec<Range<usize>>;
+}
+
+fn describe<S: Symbol>(name: &'static str, verdict: Verdict<S>) -> (&'static str, bool, Option<String>) {
+    (name, verdict.holds, verdict.witness.map(|w| w.to_string()))
+}
+
+fn outside<S: GridSymbol>(alphabet: &Alphabet<S>, input: &str) -> Vec<String> {
+    S::parse_input(input)
+        .into_iter()
+        .filter(|s| !alphabet.contains(s))
+        .map(|s| s.to_string())
+        .collect()
+}
+
+impl<S: GridSymbol> GridAutomat for DAS<S>
+where
+    str: Haystack<S>,
+{
+    fn errors(&self, allow_partial: bool) -> Vec<ValidationError<String>> {
+        self.validate_with(allow_partial).into_iter().map(ValidationError::from).collect()
+    }
+
+    fn symbols_outside_alphabet(&self, input: &str) -> Vec<String> {
+        outside(self.alphabet(), input)
+    }
+
+    fn accepts_input(&self, input: &str) -> bool {
+        self.accepts(S::parse_input(input))
+    }
+
+    fn reachable_states(&self) -> HashSet<String> {
+        DAS::reachable_states(self)
+    }
+
+    fn coreachable_states(&self) -> HashSet<String> {
+        DAS::coreachable_states(self)
+    }
+
+    fn trimmed_states(&self) -> HashSet<String> {
+        self.trim().get_states().keys().cloned().collect()
+    }
+
+    fn language_properties(&self) -> Vec<(&'static str, bool, Option<String>)> {
+        vec![
+            describe("Język pusty", self.is_empty()),
+            describe("Język uniwersalny", self.is_universal()),
+            describe("Język skończony", self.is_finite()),
+            describe("Akceptuje ε", self.accepts_epsilon()),
+        ]
+    }
+
+    fn sample_words(&self, count: usize, max_len: usize) -> (Vec<String>, Vec<String>) {
+        (
+            self.accepted_words(max_len).take(count).map(|w| format_word(&w)).collect(),
+            self.rejected_words(max_len).take(count).map(|w| format_word(&w)).collect(),
+        )
+    }
+
+    fn word_counts(&self, max_len: usize) -> (Vec<String>, String, String) {
+        let counts = self.count_words_up_to(max_len).iter().map(|c| c.to_string()).collect();
+        let recurrence = self.recurrence();
+        (counts, recurrence.to_string(), recurrence.generating_function().to_string())
+    }
+
+    fn find_matches(&self, text: &str, kind: MatchKind) -> Vec<Range<usize>> {
+        self.find_iter_with(text, kind).map(|m| m.range()).collect()
+    }
+}
+
+impl<S: GridSymbol> GridAutomat for ENAS<S>
+where
+    str: Haystack<S>,
+{
+    // ε-NAS nie musi być pełny – `allow_partial` nie ma znaczenia
+    fn errors(&self, _allow_partial: bool) -> Vec<ValidationError<String>> {
+        self.validate().into_iter().map(ValidationError::from).collect()
+    }
+
+    fn symbols_outside_alphabet(&self, input: &str) -> Vec<String> {
+        outside(self.alphabet(), input)
+    }
+
+    fn accepts_input(&self, input: &str) -> bool {
+        self.accepts(S::parse_input(input))
+    }
+
+    fn reachable_states(&self) -> HashSet<String> {
+        ENAS::reachable_states(self)
+    }
+
+    fn coreachable_states(&self) -> HashSet<String> {
+        ENAS::coreachable_states(self)
+    }
+
+    fn trimmed_states(&self) -> HashSet<String> {
+        self.trim().get_states().keys().cloned().collect()
+    }
+
+    fn language_properties(&self) -> Vec<(&'static str, bool, Option<String>)> {
+        vec![
+            describe("Język pusty", self.is_empty()),
+            describe("Język uniwersalny", self.is_universal()),
+            describe("Język skończony", self.is_finite()),
+            describe("Akceptuje ε", self.accepts_epsilon()),
+        ]
+    }
+
+    fn sample_words(&self, count: usize, max_len: usize) -> (Vec<String>, Vec<String>) {
+        (
+            self.accepted_words(max_len).take(count).map(|w| format_word(&w)).collect(),
+            self.rejected_words(max_len).take(count).map(|w| format_word(&w)).collect(),
+        )
+    }
+
+    fn word_counts(&self, max_len: usize) -> (Vec<String>, String, String) {
+        self.determinize().word_counts(max_len)
+    }
+
+    fn find_matches(&self, text: &str, kind: MatchKind) -> Vec<Range<usize>> {
+        self.find_iter_with(text, kind).map(|m| m.range()).collect()
+    }
+}
@@ -0,0 +1,84 @@
+use eframe::egui;
+
+pub enum ImportAction {
+    None,
+    Close,
+    // Zawartość wskazanego pliku i plików dodatkowych (None, gdy nie podano
+    // ścieżki) – interpretuje ją wywołujący
+    Read(String, Vec<Option<String>>),
+}
+
+// Okno wczytywania pliku; błąd odczytu lub formatu wyświetlany jest pod polem
+pub struct ImportWindow {
+    title: String,
+    path: String,
+    // Opcjonalne pliki dodatkowe (np. tabele symboli): opis i ścieżka
+    extra: Vec<(String, String)>,
+    error: Option<String>,
+}
+
+impl ImportWindow {
+    pub fn new(title: &str, file_name: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            path: file_name.to_string(),
+            extra: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn with_extra(mut self, label: &str, path: &str) -> Self {
+        self.extra.push((label.to_string(), path.to_string()));
+        self
+    }
+
+    // Ścieżka pliku głównego i plików dodatkowych (np. do listy ostatnich plików)
+    pub fn paths(&self) -> (String, Vec<String>) {
+        (self.path.clone(), self.extra.iter().map(|(_, path)| path.clone()).collect())
+    }
+
+    pub fn set_error(&mut self, message: String) {
+        self.error = Some(message);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> ImportAction {
+        let mut open = true;
+        let mut action = ImportAction::None;
+        egui::Window::new(&self.title).open(&mut open).show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Plik:");
+                ui.text_edit_singleline(&mut self.path);
+                if ui.button("Wczytaj").clicked() {
+                    match self.read() {
+                        Ok((text, extra)) => action = ImportAction::Read(text, extra),
+                        Err(message) => self.error = Some(message),
+                    }
+                }
+            });
+            for (label, path) in &mut self.extra {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} (opcjonalnie):", label));
+                    ui.text_edit_singleline(path);
+                });
+            }
+            if let Some(message) = &self.error {
+                ui.colored_label(egui::Color32::RED, message);
+            }
+        });
+        if !open {
+            return ImportAction::Close;
+        }
+        action
+    }
+
+    fn read(&self) -> Result<(String, Vec<Option<String>>), String> {
+        let read = |path: &str| std::fs::read_to_string(path).map_err(|e| format!("Nie można odczytać '{}': {}", path, e));
+        let text = read(&self.path)?;
+        let extra = self
+            .extra
+            .iter()
+            .map(|(_, path)| if path.trim().is_empty() { Ok(None) } else { read(path.trim()).map(Some) })
+            .collect::<Result<_, _>>()?;
+        Ok((text, extra))
+    }
+}
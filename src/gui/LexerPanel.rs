@@ -0,0 +1,158 @@
+use eframe::egui;
+
+use rust_state_machine::automats::ENAS::ENAS;
+use rust_state_machine::lexer::Lexer::{LexemeKind, Lexer, LexerBuilder};
+
+use crate::gui::Widgets::highlighted_text;
+
+// Źródło reguły: wyrażenie regularne albo automat zapisany z tabeli
+enum RuleSource {
+    Regex(String),
+    Automat(ENAS),
+}
+
+struct LexerRule {
+    name: String,
+    source: RuleSource,
+    skip: bool,
+}
+
+impl LexerRule {
+    fn regex(name: &str, pattern: &str, skip: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            source: RuleSource::Regex(pattern.to_string()),
+            skip,
+        }
+    }
+}
+
+// Panel "Lekser": lista reguł w kolejności priorytetu i podział tekstu na leksemy
+pub struct LexerPanel {
+    rules: Vec<LexerRule>,
+    input: String,
+}
+
+impl Default for LexerPanel {
+    fn default() -> Self {
+        Self {
+            rules: vec![
+                LexerRule::regex("ID", "[a-zA-Z_][a-zA-Z0-9_]*", false),
+                LexerRule::regex("NUM", "[0-9]+", false),
+                LexerRule::regex("WS", "[ \\t\\n]+", true),
+            ],
+            input: String::new(),
+        }
+    }
+}
+
+impl LexerPanel {
+    // Dodaje automat z tabeli jako regułę o najniższym priorytecie
+    pub fn add_automat(&mut self, enas: ENAS) {
+        self.rules.push(LexerRule {
+            name: format!("AUTOMAT{}", self.rules.len() + 1),
+            source: RuleSource::Automat(enas),
+            skip: false,
+        });
+    }
+
+    fn build(&self) -> Result<Lexer, String> {
+        let mut builder = LexerBuilder::new();
+        for rule in &self.rules {
+            match &rule.source {
+                RuleSource::Regex(pattern) => {
+                    builder.regex(&rule.name, pattern, rule.skip)?;
+                }
+                RuleSource::Automat(enas) => {
+                    builder.rule(&rule.name, enas.clone(), rule.skip);
+                }
+            }
+        }
+        Ok(builder.build())
+    }
+
+    // Zwraca true, gdy użytkownik chce dodać bieżący automat jako regułę
+    pub fn show(&mut self, ui: &mut egui::Ui, can_add_automat: bool) -> bool {
+        let mut add_automat = false;
+        let mut swap = None;
+        let mut remove = None;
+        egui::Grid::new("lexer_rules").show(ui, |ui| {
+            ui.label("Nazwa");
+            ui.label("Wyrażenie");
+            ui.label("Pomiń");
+            ui.end_row();
+            let count = self.rules.len();
+            for (i, rule) in self.rules.iter_mut().enumerate() {
+                ui.add(egui::TextEdit::singleline(&mut rule.name).desired_width(80.0));
+                match &mut rule.source {
+                    RuleSource::Regex(pattern) => {
+                        ui.add(egui::TextEdit::singleline(pattern).code_editor().desired_width(200.0));
+                    }
+                    RuleSource::Automat(enas) => {
+                        ui.label(format!("automat ({} stanów)", enas.get_states().len()));
+                    }
+                }
+                ui.checkbox(&mut rule.skip, "");
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(i > 0, egui::Button::new("▲").small()).clicked() {
+                        swap = Some(i - 1);
+                    }
+                    if ui.add_enabled(i + 1 < count, egui::Button::new("▼").small()).clicked() {
+                        swap = Some(i);
+                    }
+                    if ui.small_button("✖").clicked() {
+                        remove = Some(i);
+                    }
+                });
+                ui.end_row();
+            }
+        });
+        if let Some(i) = swap {
+            self.rules.swap(i, i + 1);
+        }
+        if let Some(i) = remove {
+            self.rules.remove(i);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Dodaj regułę").clicked() {
+                self.rules.push(LexerRule::regex("", "", false));
+            }
+            if ui
+                .add_enabled(can_add_automat, egui::Button::new("Dodaj bieżący automat"))
+                .on_disabled_hover_text("Wymaga poprawnego automatu w trybie znaków")
+                .clicked()
+            {
+                add_automat = true;
+            }
+        });
+        ui.label("Reguły wyżej na liście mają wyższy priorytet.");
+
+        match self.build() {
+            Ok(lexer) => {
+                let lexemes = lexer.tokenize(&self.input);
+                highlighted_text(ui, &mut self.input, |text| {
+                    lexer
+                        .tokenize(text)
+                        .into_iter()
+                        .filter(|l| l.kind != LexemeKind::Error)
+                        .map(|l| l.span)
+                        .collect()
+                });
+                egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                    for lexeme in lexemes {
+                        if lexeme.kind == LexemeKind::Error {
+                            ui.colored_label(egui::Color32::RED, lexeme.to_string());
+                        } else {
+                            ui.label(lexeme.to_string());
+                        }
+                    }
+                });
+            }
+            Err(message) => {
+                ui.colored_label(egui::Color32::RED, message);
+            }
+        }
+        add_automat
+    }
+}
@@ -0,0 +1,69 @@
+use eframe::egui;
+
+use rust_state_machine::automats::ENAS::ENAS;
+use rust_state_machine::formats::{Automat, Dsl};
+
+// Zakładka "Tekst": automat w zapisie tekstowym (`formats::Dsl`). Poprawny
+// tekst po każdej zmianie trafia do tabeli; błąd pokazywany jest z pozycją.
+#[derive(Default)]
+pub struct TextEditor {
+    text: String,
+    error: Option<String>,
+    // Tabela nie daje się zapisać tekstowo (błędy, tryb tokenów)
+    outdated: Option<String>,
+}
+
+impl TextEditor {
+    pub fn set_automat(&mut self, enas: &ENAS) {
+        self.text = Dsl::write(enas);
+        self.error = None;
+        self.outdated = None;
+    }
+
+    // Błąd tekstu, którego nie da się przenieść do tabeli
+    pub fn set_error(&mut self, message: String) {
+        self.error = Some(message);
+    }
+
+    pub fn set_outdated(&mut self, message: &str) {
+        self.outdated = Some(message.to_string());
+    }
+
+    // Zwraca automat, gdy zmieniony tekst jest poprawny
+    pub fn show(&mut self, ui: &mut egui::Ui) -> Option<Automat> {
+        let mut parsed = None;
+        ui.horizontal(|ui| {
+            if ui.button("Formatuj").on_hover_text("Zapis kanoniczny: alfabet, start, akceptujące, przejścia").clicked() {
+                match Dsl::format(&self.text) {
+                    Ok(text) => self.text = text,
+                    Err(e) => self.error = Some(e.to_string()),
+                }
+            }
+            ui.label("Instrukcje: alphabet a b; start q0; accept q2; q0 -a-> q1; q1 -ε-> q2")
+                .on_hover_text("Instrukcje oddziela średnik albo koniec wiersza; '#' rozpoczyna komentarz.\nNazwy ze znakami specjalnymi w cudzysłowie (\"q 1\"), symbole w apostrofach (' ', '-').");
+        });
+        if let Some(message) = &self.outdated {
+            ui.colored_label(egui::Color32::from_rgb(230, 140, 0), message);
+        }
+        let response = ui.add(
+            egui::TextEdit::multiline(&mut self.text)
+                .code_editor()
+                .desired_rows(12)
+                .desired_width(f32::INFINITY),
+        );
+        if response.changed() {
+            match Dsl::parse(&self.text) {
+                Ok(automat) => {
+                    self.error = None;
+                    self.outdated = None;
+                    parsed = Some(automat);
+                }
+                Err(e) => self.error = Some(e.to_string()),
+            }
+        }
+        if let Some(message) = &self.error {
+            ui.colored_label(egui::Color32::RED, message);
+        }
+        parsed
+    }
+}
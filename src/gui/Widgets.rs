@@ -0,0 +1,119 @@
+use eframe::egui;
+use std::ops::Range;
+
+use crate::parse_targets;
+
+// Pole tekstowe z listą rozwijaną istniejących stanów (DAS).
+// Podczas pisania pod polem pojawiają się pasujące nazwy stanów.
+pub fn target_combo(ui: &mut egui::Ui, cell: &mut String, states: &[String]) -> egui::Response {
+    ui.horizontal(|ui| {
+        let response = ui.add(egui::TextEdit::singleline(cell).desired_width(60.0));
+
+        // Autouzupełnianie
+        let popup_id = response.id.with("suggestions");
+        let suggestions: Vec<&String> = states
+            .iter()
+            .filter(|s| !s.is_empty() && *s != cell && s.starts_with(cell.as_str()))
+            .collect();
+        if (response.gained_focus() || response.changed()) && !suggestions.is_empty() {
+            ui.memory_mut(|m| m.open_popup(popup_id));
+        } else if suggestions.is_empty() && ui.memory(|m| m.is_popup_open(popup_id)) {
+            ui.memory_mut(|m| m.close_popup());
+        }
+        egui::popup_below_widget(ui, popup_id, &response, |ui| {
+            for s in suggestions {
+                if ui.selectable_label(false, s).clicked() {
+                    *cell = s.clone();
+                    ui.memory_mut(|m| m.close_popup());
+                }
+            }
+        });
+
+        // Lista wszystkich stanów
+        ui.menu_button("▾", |ui| {
+            for s in states.iter().filter(|s| !s.is_empty()) {
+                if ui.selectable_label(cell == s, s).clicked() {
+                    *cell = s.clone();
+                    ui.close_menu();
+                }
+            }
+        });
+        response
+    })
+    .inner
+}
+
+// Edytor wielokrotnego wyboru (ε-NAS): każdy stan docelowy to osobny "chip",
+// kliknięcie usuwa go, a menu "+" pozwala zaznaczyć kolejne stany.
+// Zawartość komórki pozostaje listą nazw oddzielonych przecinkami.
+pub fn target_chips(ui: &mut egui::Ui, cell: &mut String, states: &[String]) -> egui::Response {
+    let mut targets = parse_targets(cell);
+    let mut changed = false;
+    let inner = ui.horizontal(|ui| {
+        let mut removed = None;
+        for (i, t) in targets.iter().enumerate() {
+            if ui.small_button(format!("{} ×", t)).on_hover_text("Usuń").clicked() {
+                removed = Some(i);
+            }
+        }
+        if let Some(i) = removed {
+            targets.remove(i);
+            changed = true;
+        }
+        ui.menu_button("+", |ui| {
+            for s in states.iter().filter(|s| !s.is_empty()) {
+                let mut selected = targets.contains(s);
+                if ui.checkbox(&mut selected, s.as_str()).changed() {
+                    if selected {
+                        targets.push(s.clone());
+                    } else {
+                        targets.retain(|t| t != s);
+                    }
+                    changed = true;
+                }
+            }
+        });
+    });
+    if changed {
+        *cell = targets.join(", ");
+    }
+    inner.response.interact(egui::Sense::click())
+}
+
+// Wielowierszowe pole tekstowe z podświetlonymi zakresami bajtów zwróconymi
+// przez `find`. Sąsiednie dopasowania mają na przemian różne kolory tła.
+pub fn highlighted_text(
+    ui: &mut egui::Ui,
+    text: &mut String,
+    mut find: impl FnMut(&str) -> Vec<Range<usize>>,
+) -> egui::Response {
+    let highlights = [
+        egui::Color32::from_rgb(255, 220, 100),
+        egui::Color32::from_rgb(150, 210, 255),
+    ];
+    let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+        let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+        let plain = egui::TextFormat::simple(font_id.clone(), ui.visuals().text_color());
+        let mut job = egui::text::LayoutJob::default();
+        let mut position = 0;
+        for (i, range) in find(text).into_iter().enumerate() {
+            job.append(&text[position..range.start], 0.0, plain.clone());
+            let format = egui::TextFormat {
+                background: highlights[i % highlights.len()],
+                color: egui::Color32::BLACK,
+                ..egui::TextFormat::simple(font_id.clone(), egui::Color32::BLACK)
+            };
+            job.append(&text[range.clone()], 0.0, format);
+            position = range.end;
+        }
+        job.append(&text[position..], 0.0, plain);
+        job.wrap.max_width = wrap_width;
+        ui.fonts(|f| f.layout_job(job))
+    };
+    ui.add(
+        egui::TextEdit::multiline(text)
+            .desired_width(f32::INFINITY)
+            .desired_rows(6)
+            .layouter(&mut layouter),
+    )
+}
@@ -0,0 +1,7 @@
+pub mod Widgets;
+pub mod GridAutomat;
+pub mod LexerPanel;
+pub mod ExportWindow;
+pub mod ImportWindow;
+pub mod TextEditor;
+pub mod Analysis;
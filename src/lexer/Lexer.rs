@@ -0,0 +1,289 @@
+use crate::elements::Alphabet::Alphabet;
+use crate::elements::Label::Label;
+use crate::elements::Node::{DASNode, ENASNode, NodeBase};
+use crate::automats::DAS::DAS;
+use crate::automats::ENAS::ENAS;
+use crate::automats::Runner::Runner;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::iter;
+use std::ops::Range;
+
+// Reguła leksera; reguły pominięte (`skip`, np. białe znaki) są rozpoznawane,
+// ale nie trafiają do wyniku
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rule {
+    pub name: String,
+    pub skip: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LexemeKind {
+    Rule(String),
+    // Fragment wejścia, którego nie rozpoznaje żadna reguła
+    Error,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lexeme {
+    pub kind: LexemeKind,
+    pub text: String,
+    // Zakres bajtów w wejściu
+    pub span: Range<usize>,
+}
+
+impl fmt::Display for Lexeme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            LexemeKind::Rule(name) => write!(f, "{} {:?} {}..{}", name, self.text, self.span.start, self.span.end),
+            LexemeKind::Error => write!(f, "BŁĄD {:?} {}..{}", self.text, self.span.start, self.span.end),
+        }
+    }
+}
+
+// Reguły w kolejności dodania – wcześniejsza reguła ma wyższy priorytet,
+// gdy dwie rozpoznają ten sam najdłuższy fragment
+#[derive(Clone, Default)]
+pub struct LexerBuilder {
+    rules: Vec<(Rule, ENAS)>,
+}
+
+impl LexerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rule(&mut self, name: &str, enas: ENAS, skip: bool) -> &mut Self {
+        let rule = Rule {
+            name: name.to_string(),
+            skip,
+        };
+        self.rules.push((rule, enas));
+        self
+    }
+
+    pub fn regex(&mut self, name: &str, pattern: &str, skip: bool) -> Result<&mut Self, String> {
+        let enas = ENAS::from_regex(pattern).map_err(|e| format!("Reguła '{}': {}", name, e))?;
+        Ok(self.rule(name, enas, skip))
+    }
+
+    // Reguły zapisane tekstowo, po jednej w wierszu:
+    //   NAZWA = wyrażenie
+    //   skip NAZWA = wyrażenie
+    // Puste wiersze i wiersze zaczynające się od '#' są pomijane.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut builder = Self::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, pattern)) = line.split_once('=') else {
+                return Err(format!("Wiersz {}: oczekiwano 'NAZWA = wyrażenie'.", i + 1));
+            };
+            let (name, skip) = match name.trim().strip_prefix("skip ") {
+                Some(name) => (name.trim(), true),
+                None => (name.trim(), false),
+            };
+            if name.is_empty() {
+                return Err(format!("Wiersz {}: brak nazwy reguły.", i + 1));
+            }
+            builder.regex(name, pattern.trim(), skip).map_err(|e| format!("Wiersz {}: {}", i + 1, e))?;
+        }
+        Ok(builder)
+    }
+
+    // Łączy reguły w jeden ε-NAS (nowy stan startowy z przejściami ε do
+    // automatów reguł) i determinizuje go. Stan akceptujący DAS dostaje
+    // etykietę reguły o najwyższym priorytecie spośród zawartych w nim stanów.
+    pub fn build(&self) -> Lexer {
+        let mut alphabet = Alphabet::new();
+        for symbol in self.rules.iter().flat_map(|(_, enas)| enas.alphabet()) {
+            alphabet.add(*symbol);
+        }
+        let start = "start".to_string();
+        let mut combined = ENAS::new(alphabet.clone());
+        let mut rule_of_state = HashMap::new();
+        let mut starts = Vec::new();
+        for (i, (_, enas)) in self.rules.iter().enumerate() {
+            let rename = |name: &str| format!("{}:{}", i, name);
+            starts.push(rename(enas.get_start_state()));
+            for (name, node) in enas.get_states() {
+                let mut renamed = ENASNode::new(&rename(name), node.is_accepting());
+                for (label, targets) in node.get_connections() {
+                    renamed.add_connection(*label, targets.iter().map(|t| rename(t)).collect());
+                }
+                if node.is_accepting() {
+                    rule_of_state.insert(rename(name), i);
+                }
+                combined.add_state(renamed);
+            }
+        }
+        let mut start_node = ENASNode::new(&start, false);
+        start_node.add_connection(Label::Epsilon, starts);
+        combined.add_state(start_node);
+        combined.set_start_state(&start);
+
+        // Konstrukcja podzbiorów z zapamiętaniem reguły dla każdego stanu DAS
+        let symbols = alphabet.symbols();
+        let mut das = DAS::new(alphabet);
+        let mut ids: HashMap<BTreeSet<String>, String> = HashMap::new();
+        let mut labels = HashMap::new();
+        let mut queue = VecDeque::new();
+        let first: BTreeSet<String> = combined.epsilon_closure(&[start]).into_iter().collect();
+        ids.insert(first.clone(), "d0".to_string());
+        queue.push_back(first);
+        while let Some(set) = queue.pop_front() {
+            let name = ids[&set].clone();
+            let rule = set.iter().filter_map(|s| rule_of_state.get(s)).min();
+            if let Some(&rule) = rule {
+                labels.insert(name.clone(), rule);
+            }
+            let mut node = DASNode::new(&name, rule.is_some());
+            let states: HashSet<String> = set.into_iter().collect();
+            for symbol in &symbols {
+                let next: BTreeSet<String> = combined.step(&states, symbol).into_iter().collect();
+                if next.is_empty() {
+                    continue;
+                }
+                let count = ids.len();
+                let target = ids.entry(next.clone()).or_insert_with(|| {
+                    queue.push_back(next);
+                    format!("d{}", count)
+                });
+                node.add_connection(*symbol, target);
+            }
+            das.add_state(node);
+        }
+        das.set_start_state("d0");
+
+        Lexer {
+            rules: self.rules.iter().map(|(rule, _)| rule.clone()).collect(),
+            das,
+            labels,
+        }
+    }
+}
+
+pub struct Lexer {
+    rules: Vec<Rule>,
+    das: DAS,
+    // Stan akceptujący DAS → indeks reguły
+    labels: HashMap<String, usize>,
+}
+
+impl Lexer {
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    // Połączony automat wszystkich reguł
+    pub fn automaton(&self) -> &DAS {
+        &self.das
+    }
+
+    // Reguła rozpoznawana w danym stanie akceptującym
+    pub fn rule_of_state(&self, state: &str) -> Option<&Rule> {
+        self.labels.get(state).map(|&i| &self.rules[i])
+    }
+
+    // Podział na leksemy metodą najdłuższego dopasowania. Nierozpoznane znaki
+    // są pomijane pojedynczo i łączone w jeden leksem błędu, po czym
+    // rozpoznawanie jest wznawiane od kolejnego znaku.
+    pub fn tokenize(&self, input: &str) -> Vec<Lexeme> {
+        let chars: Vec<(usize, char)> = input.char_indices().collect();
+        let offset = |i: usize| chars.get(i).map_or(input.len(), |&(o, _)| o);
+        let mut runner = self.das.start();
+        let mut lexemes: Vec<Lexeme> = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            // (indeks za ostatnim znakiem, reguła) najdłuższego dopasowania
+            let mut best = None;
+            runner.reset();
+            for (j, &(_, c)) in chars.iter().enumerate().skip(i) {
+                let alive = runner.feed(iter::once(c));
+                if let Some(&rule) = runner.state().and_then(|s| self.labels.get(s)) {
+                    best = Some((j + 1, rule));
+                }
+                if !alive {
+                    break;
+                }
+            }
+
+            let Some((end, rule)) = best else {
+                let span = offset(i)..offset(i + 1);
+                match lexemes.last_mut() {
+                    Some(last) if last.kind == LexemeKind::Error && last.span.end == span.start => {
+                        last.span.end = span.end;
+                        last.text = input[last.span.clone()].to_string();
+                    }
+                    _ => lexemes.push(Lexeme {
+                        kind: LexemeKind::Error,
+                        text: input[span.clone()].to_string(),
+                        span,
+                    }),
+                }
+                i += 1;
+                continue;
+            };
+            let rule = &self.rules[rule];
+            if !rule.skip {
+                let span = offset(i)..offset(end);
+                lexemes.push(Lexeme {
+                    kind: LexemeKind::Rule(rule.name.clone()),
+                    text: input[span.clone()].to_string(),
+                    span,
+                });
+            }
+            i = end;
+        }
+        lexemes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lexer() -> Lexer {
+        LexerBuilder::parse("IF = if\nID = [a-z]+\nNUM = [0-9]+\nOP = =|==\nskip WS = [ ]+").unwrap().build()
+    }
+
+    fn lexemes(input: &str) -> Vec<(String, Range<usize>)> {
+        lexer()
+            .tokenize(input)
+            .into_iter()
+            .map(|l| match l.kind {
+                LexemeKind::Rule(name) => (name, l.span),
+                LexemeKind::Error => ("BŁĄD".to_string(), l.span),
+            })
+            .collect()
+    }
+
+    fn lexeme(name: &str, span: Range<usize>) -> (String, Range<usize>) {
+        (name.to_string(), span)
+    }
+
+    #[test]
+    fn maximal_munch_and_priority() {
+        assert_eq!(
+            lexemes("if iffy == 42"),
+            [lexeme("IF", 0..2), lexeme("ID", 3..7), lexeme("OP", 8..10), lexeme("NUM", 11..13)]
+        );
+    }
+
+    #[test]
+    fn errors_are_merged_and_recovered_from() {
+        assert_eq!(
+            lexemes("ab $%ż 12"),
+            [lexeme("ID", 0..2), lexeme("BŁĄD", 3..7), lexeme("NUM", 8..10)]
+        );
+        assert_eq!(lexer().tokenize("x#")[1].text, "#");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(LexerBuilder::parse("# reguły\nID [a-z]+").err().unwrap(), "Wiersz 2: oczekiwano 'NAZWA = wyrażenie'.");
+        assert!(LexerBuilder::parse("ID = (a").err().unwrap().starts_with("Wiersz 1: Reguła 'ID': "));
+    }
+}
@@ -0,0 +1,9 @@
+#![allow(non_snake_case)]
+#![allow(clippy::upper_case_acronyms)]
+
+pub mod elements;
+pub mod automats;
+pub mod lexer;
+pub mod codegen;
+pub mod formats;
+pub mod diagram;
@@ -1,19 +1,218 @@
+#![allow(non_snake_case)]
+#![allow(clippy::upper_case_acronyms)]
+
 use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::rc::Rc;
+
+mod cli;
+mod gui;
 
-mod elements;
-mod automats;
+use rust_state_machine::elements::Alphabet::Alphabet;
+use rust_state_machine::elements::Node::{DASNode, ENASNode, NodeBase};
+use rust_state_machine::elements::Label::Label;
+use rust_state_machine::elements::Symbol::{Token, EPSILON};
+use rust_state_machine::automats::DAS::{sink_name, DAS};
+use rust_state_machine::automats::ENAS::ENAS;
+use rust_state_machine::automats::ValidationError::ValidationError;
+use rust_state_machine::automats::Search::MatchKind;
+use rust_state_machine::codegen::Backend;
+use rust_state_machine::diagram::Layout::LayoutKind;
+use rust_state_machine::diagram::{Png, Svg};
+use rust_state_machine::formats::Table::{TableRow, TransitionTable};
+use rust_state_machine::formats::{ordered_states, Att, Automat, Jflap, Mermaid, PlantUml, Tikz};
+use gui::Analysis::Analysis;
+use gui::GridAutomat::{GridAutomat, GridSymbol};
+use gui::ExportWindow::ExportWindow;
+use gui::ImportWindow::{ImportAction, ImportWindow};
+use gui::LexerPanel::LexerPanel;
+use gui::TextEditor::TextEditor;
+use gui::Widgets::{highlighted_text, target_chips, target_combo};
 
-use elements::Alphabet::Alphabet;
-use elements::Node::{DASNode, ENASNode};
-use automats::DAS::DAS;
-use automats::ENAS::ENAS;
+// Sposób edycji automatu: tabela albo zapis tekstowy
+#[derive(PartialEq, Clone, Copy, Serialize, Deserialize)]
+enum EditorView {
+    Table,
+    Text,
+}
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy, Serialize, Deserialize)]
 enum AutomatType {
     DAS,
     ENAS,
 }
 
+// Rodzaj symboli: pojedyncze znaki albo tokeny oddzielone spacjami
+#[derive(PartialEq, Clone, Copy, Serialize, Deserialize)]
+enum SymbolMode {
+    Chars,
+    Tokens,
+}
+
+// Błąd wyświetlany w GUI – pochodzi z walidacji automatu albo z samej tabeli
+enum GridError {
+    Automat(ValidationError<String>),
+    EmptyAlphabetCell(usize),
+    InvalidAlphabetCell(usize, String),
+    OverlappingSymbol(usize, String),
+    SymbolOutsideAlphabet(String),
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Automat(error) => write!(f, "{}", error),
+            GridError::EmptyAlphabetCell(i) => {
+                write!(f, "Pole na symbol alfabetu w kolumnie {} jest puste.", i + 1)
+            }
+            GridError::InvalidAlphabetCell(i, message) => {
+                write!(f, "Kolumna alfabetu {}: {}", i + 1, message)
+            }
+            GridError::OverlappingSymbol(i, c) => {
+                write!(f, "Symbol '{}' w kolumnie {} występuje już w innej kolumnie alfabetu.", c, i + 1)
+            }
+            GridError::SymbolOutsideAlphabet(c) => {
+                write!(f, "Ciąg wejściowy zawiera symbol '{}' spoza alfabetu.", c)
+            }
+        }
+    }
+}
+
+// Miejsce w interfejsie, którego dotyczy błąd
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+enum ErrorLocation {
+    // (indeks stanu, indeks kolumny przejścia)
+    Transition(usize, usize),
+    AlphabetCell(usize),
+    Input,
+}
+
+// Szybkie poprawki proponowane przy błędach
+enum QuickFix {
+    CreateState(String),
+    // Komórka (wiersz, kolumna) i nazwa ujścia
+    RouteToSink(usize, usize, String),
+}
+
+impl QuickFix {
+    fn label(&self) -> String {
+        match self {
+            QuickFix::CreateState(name) => format!("Utwórz stan '{}'", name),
+            QuickFix::RouteToSink(_, _, sink) => format!("Skieruj do ujścia '{}'", sink),
+        }
+    }
+}
+
+// Formaty dostępne w menu "Import"
+#[derive(PartialEq, Clone, Copy, Serialize, Deserialize)]
+enum ImportFormat {
+    Jflap,
+    Csv,
+    Tsv,
+    Att,
+}
+
+impl ImportFormat {
+    const ALL: [ImportFormat; 4] = [ImportFormat::Jflap, ImportFormat::Csv, ImportFormat::Tsv, ImportFormat::Att];
+
+    fn label(self) -> &'static str {
+        match self {
+            ImportFormat::Jflap => "JFLAP (.jff)",
+            ImportFormat::Csv => "Tabela CSV",
+            ImportFormat::Tsv => "Tabela TSV",
+            ImportFormat::Att => "OpenFst (AT&T)",
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            ImportFormat::Jflap => "automat.jff",
+            ImportFormat::Csv => "automat.csv",
+            ImportFormat::Tsv => "automat.tsv",
+            ImportFormat::Att => "automat.fst.txt",
+        }
+    }
+
+    // Opisy opcjonalnych plików wczytywanych razem z głównym
+    fn extra_files(self) -> &'static [&'static str] {
+        match self {
+            ImportFormat::Att => &["Tabela symboli wejściowych", "Tabela nazw stanów"],
+            _ => &[],
+        }
+    }
+}
+
+// Arkusze z polskimi ustawieniami zapisują CSV ze średnikami
+fn csv_delimiter(text: &str) -> char {
+    let header = text.lines().next().unwrap_or_default();
+    if header.matches(';').count() > header.matches(',').count() { ';' } else { ',' }
+}
+
+// ε-NAS, gdy tabela ma kolumnę ε albo komórkę z kilkoma stanami docelowymi
+// (przecinek w nazwie istniejącego stanu, np. "{q0,q1}", się nie liczy)
+fn table_type(table: &TransitionTable) -> AutomatType {
+    let is_state = |cell: &String| table.rows.iter().any(|r| &r.state == cell);
+    let multiple = table.rows.iter().flat_map(|r| &r.cells).any(|c| c.contains(',') && !is_state(c));
+    if table.epsilon || multiple { AutomatType::ENAS } else { AutomatType::DAS }
+}
+
+// Tabela w GUI ma zawsze co najmniej jeden stan (pierwszy wiersz to stan
+// startowy) i jedną kolumnę alfabetu – dotyczy wczytanych sesji i importu
+fn check_table_size(rows: usize, symbols: usize) -> Result<(), String> {
+    if rows == 0 {
+        Err("Tabela nie zawiera żadnego stanu".to_string())
+    } else if symbols == 0 {
+        Err("Tabela nie zawiera żadnego symbolu alfabetu".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+// Liczba pamiętanych ostatnio wczytanych plików
+const RECENT_FILES: usize = 8;
+
+#[derive(Clone, Serialize, Deserialize)]
+struct RecentFile {
+    format: ImportFormat,
+    path: String,
+    // Ścieżki plików dodatkowych (`ImportFormat::extra_files`), puste – nie podano
+    extra: Vec<String>,
+}
+
+// Stan zapisywany między uruchomieniami: ostatni automat, ustawienia edytora,
+// język generowanego kodu i ostatnie pliki (układ okien zapisuje sam eframe)
+#[derive(Serialize, Deserialize)]
+struct Session {
+    automat_type: AutomatType,
+    symbol_mode: SymbolMode,
+    editor_view: EditorView,
+    text_editing: bool,
+    allow_partial: bool,
+    alphabet_cells: Vec<String>,
+    state_names: Vec<String>,
+    transitions: Vec<Vec<String>>,
+    accepting_states: Vec<bool>,
+    state_positions: Vec<Option<(f32, f32)>>,
+    input_string: String,
+    code_language: String,
+    recent_files: Vec<RecentFile>,
+}
+
+// Wszystko, od czego zależy automat zbudowany z tabeli i jego walidacja
+#[derive(Clone, PartialEq)]
+struct TableKey {
+    alphabet_cells: Vec<String>,
+    state_names: Vec<String>,
+    transitions: Vec<Vec<String>>,
+    accepting_states: Vec<bool>,
+    state_positions: Vec<Option<(f32, f32)>>,
+    automat_type: AutomatType,
+    symbol_mode: SymbolMode,
+    allow_partial: bool,
+}
+
 struct MyApp {
     num_rows: usize,
     num_columns: usize,
@@ -21,10 +220,48 @@ struct MyApp {
     state_names: Vec<String>,
     transitions: Vec<Vec<String>>,
     accepting_states: Vec<bool>,
+    // Położenia stanów na diagramie (np. z pliku JFLAP), równoległe do wierszy
+    state_positions: Vec<Option<(f32, f32)>>,
     input_string: String,
     result: Option<bool>,
-    validation_message: String,
+    errors: Vec<GridError>,
+    // Ciąg wejściowy, dla którego policzono `errors`
+    checked_input: Option<String>,
+    // Automat z tabeli w postaci, dla której go zbudowano
+    table_automat: Option<(TableKey, Rc<dyn GridAutomat>)>,
+    // Wyniki analizy osiągalności i paneli dla `table_automat`
+    reachable: HashSet<String>,
+    coreachable: HashSet<String>,
+    analysis: Analysis,
     automat_type: AutomatType,
+    symbol_mode: SymbolMode,
+    // Zwykłe pola tekstowe zamiast edytora stanów docelowych (ε-NAS)
+    text_editing: bool,
+    // Niepełny DAS jest poprawny – brak przejścia oznacza odrzucenie
+    allow_partial: bool,
+    // Ustawienia panelu z przykładowymi słowami
+    sample_count: usize,
+    sample_max_len: usize,
+    // Maksymalna długość słów w panelu "Liczba słów"
+    count_max_len: usize,
+    // Tekst przeszukiwany w panelu "Wyszukiwanie w tekście"
+    search_text: String,
+    match_kind: MatchKind,
+    lexer_panel: LexerPanel,
+    editor_view: EditorView,
+    text_editor: TextEditor,
+    // Tekst do odświeżenia z tabeli (po przełączeniu widoku albo imporcie)
+    text_outdated: bool,
+    // Język ostatnio wygenerowanego kodu
+    code_language: Backend,
+    recent_files: Vec<RecentFile>,
+    // Otwarte okna eksportu i importu
+    export_window: Option<ExportWindow>,
+    // Układ stanów dla eksportu obrazu diagramu
+    image_layout: LayoutKind,
+    import_window: Option<(ImportFormat, ImportWindow)>,
+    // Błąd ostatniego wklejenia tabeli ze schowka
+    paste_error: Option<String>,
 }
 
 impl Default for MyApp {
@@ -38,264 +275,1009 @@ impl Default for MyApp {
             state_names: vec!["q0".to_string(); num_rows - 1],
             transitions: vec![vec!["".to_string(); num_columns - 1]; num_rows - 1],
             accepting_states: vec![false; num_rows - 1],
+            state_positions: vec![None; num_rows - 1],
             input_string: String::new(),
             result: None,
-            validation_message: String::new(),
+            errors: Vec::new(),
+            checked_input: None,
+            table_automat: None,
+            reachable: HashSet::new(),
+            coreachable: HashSet::new(),
+            analysis: Analysis::default(),
             automat_type: AutomatType::DAS,
+            symbol_mode: SymbolMode::Chars,
+            text_editing: false,
+            allow_partial: false,
+            sample_count: 10,
+            sample_max_len: 8,
+            count_max_len: 10,
+            search_text: String::new(),
+            match_kind: MatchKind::LeftmostLongest,
+            lexer_panel: LexerPanel::default(),
+            editor_view: EditorView::Table,
+            text_editor: TextEditor::default(),
+            text_outdated: true,
+            code_language: Backend::RustMatch,
+            recent_files: Vec::new(),
+            export_window: None,
+            image_layout: LayoutKind::Stored,
+            import_window: None,
+            paste_error: None,
         }
     }
 }
 
-impl eframe::App for MyApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            // Przełącznik typu automatu
-            ui.horizontal(|ui| {
-                ui.label("Typ automatu:");
-                ui.radio_value(&mut self.automat_type, AutomatType::DAS, "DAS");
-                ui.radio_value(&mut self.automat_type, AutomatType::ENAS, "ε-NAS");
-            });
+fn parse_targets(cell: &str) -> Vec<String> {
+    cell.split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+impl MyApp {
+    // Aplikacja z sesją zapisaną przy poprzednim zamknięciu
+    fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let mut app = Self::default();
+        if let Some(session) = cc.storage.and_then(|storage| eframe::get_value::<Session>(storage, eframe::APP_KEY)) {
+            app.restore(session);
+        }
+        app
+    }
+
+    fn session(&self) -> Session {
+        Session {
+            automat_type: self.automat_type,
+            symbol_mode: self.symbol_mode,
+            editor_view: self.editor_view,
+            text_editing: self.text_editing,
+            allow_partial: self.allow_partial,
+            alphabet_cells: self.alphabet_cells.clone(),
+            state_names: self.state_names.clone(),
+            transitions: self.transitions.clone(),
+            accepting_states: self.accepting_states.clone(),
+            state_positions: self.state_positions.clone(),
+            input_string: self.input_string.clone(),
+            code_language: self.code_language.name().to_string(),
+            recent_files: self.recent_files.clone(),
+        }
+    }
+
+    // Niespójna tabela (np. z uszkodzonego zapisu) zostaje pominięta
+    fn restore(&mut self, session: Session) {
+        if let Some(backend) = Backend::from_name(&session.code_language) {
+            self.code_language = backend;
+        }
+        self.recent_files = session.recent_files;
+        // Sesja z niespójną tabelą (np. zapisana przez inną wersję) jest
+        // pomijana w całości. Wiersz ma komórkę na każdy symbol i ewentualnie
+        // komórkę ε na końcu – w ε-NAS brakująca komórka ε jest dopisywana pusta.
+        let rows = session.state_names.len();
+        let symbols = session.alphabet_cells.len();
+        if check_table_size(rows, symbols).is_err()
+            || session.transitions.len() != rows
+            || session.transitions.iter().any(|row| row.len() != symbols && row.len() != symbols + 1)
+            || session.accepting_states.len() != rows
+            || session.state_positions.len() != rows
+        {
+            return;
+        }
+        self.automat_type = session.automat_type;
+        self.symbol_mode = session.symbol_mode;
+        self.editor_view = session.editor_view;
+        self.text_editing = session.text_editing;
+        self.allow_partial = session.allow_partial;
+        self.num_rows = rows + 1;
+        self.num_columns = symbols + 1;
+        self.alphabet_cells = session.alphabet_cells;
+        self.state_names = session.state_names;
+        self.transitions = session.transitions;
+        if self.automat_type == AutomatType::ENAS {
+            for row in &mut self.transitions {
+                row.resize(symbols + 1, String::new());
+            }
+        }
+        self.accepting_states = session.accepting_states;
+        self.state_positions = session.state_positions;
+        self.input_string = session.input_string;
+    }
+
+    // "Plik → Nowy": pusty automat; lista ostatnich plików i język kodu zostają
+    fn new_automat(&mut self) {
+        *self = Self {
+            code_language: self.code_language,
+            recent_files: std::mem::take(&mut self.recent_files),
+            ..Self::default()
+        };
+    }
+
+    fn import_window(format: ImportFormat, path: &str, extra: &[String]) -> ImportWindow {
+        let title = format!("Import: {}", format.label());
+        format
+            .extra_files()
+            .iter()
+            .enumerate()
+            .fold(ImportWindow::new(&title, path), |window, (i, label)| {
+                window.with_extra(label, extra.get(i).map_or("", String::as_str))
+            })
+    }
+
+    fn remember_file(&mut self, format: ImportFormat, path: String, extra: Vec<String>) {
+        self.recent_files.retain(|file| file.path != path);
+        self.recent_files.insert(0, RecentFile { format, path, extra });
+        self.recent_files.truncate(RECENT_FILES);
+    }
+
+    // Ponowne wczytanie ostatniego pliku; przy błędzie otwiera się okno importu
+    fn open_recent(&mut self, file: RecentFile) {
+        let read = |path: &str| std::fs::read_to_string(path).map_err(|e| format!("Nie można odczytać '{}': {}", path, e));
+        let result = read(&file.path).and_then(|text| {
+            let extra = file
+                .extra
+                .iter()
+                .map(|path| if path.is_empty() { Ok(None) } else { read(path).map(Some) })
+                .collect::<Result<Vec<_>, _>>()?;
+            self.import(file.format, &text, &extra)
+        });
+        match result {
+            Ok(()) => self.remember_file(file.format, file.path, file.extra),
+            Err(message) => {
+                let mut window = Self::import_window(file.format, &file.path, &file.extra);
+                window.set_error(message);
+                self.import_window = Some((file.format, window));
+            }
+        }
+    }
+
+    fn push_state(&mut self, name: &str) {
+        self.num_rows += 1;
+        self.state_names.push(name.to_string());
+        self.transitions.push(vec!["".to_string(); self.num_columns - 1 + if matches!(self.automat_type, AutomatType::ENAS) { 1 } else { 0 }]);
+        self.accepting_states.push(false);
+        self.state_positions.push(None);
+    }
+
+    // Symbole opisywane przez kolumnę alfabetu – w trybie znaków jeden znak,
+    // zakres (a-z) lub klasa ([0-9]), w trybie tokenów pojedynczy token
+    fn parse_column(&self, column: usize) -> Result<Vec<String>, String> {
+        fn parse<S: GridSymbol>(cell: &str) -> Result<Vec<String>, String> {
+            Ok(S::parse_column(cell)?.iter().map(|s| s.to_string()).collect())
+        }
+        match self.symbol_mode {
+            SymbolMode::Chars => parse::<char>(&self.alphabet_cells[column]),
+            SymbolMode::Tokens => parse::<Token>(&self.alphabet_cells[column]),
+        }
+    }
+
+    fn column_symbols<S: GridSymbol>(&self, column: usize) -> Vec<S> {
+        S::parse_column(&self.alphabet_cells[column]).unwrap_or_default()
+    }
+
+    fn build_alphabet<S: GridSymbol>(&self) -> Alphabet<S> {
+        let mut alphabet = Alphabet::new();
+        for j in 0..self.alphabet_cells.len() {
+            for symbol in self.column_symbols(j) {
+                alphabet.add(symbol);
+            }
+        }
+        alphabet
+    }
 
-            // Przyciski do edycji tabeli
-            if ui.button("Dodaj znak alfabetu").clicked() {
-                self.num_columns += 1;
-                self.alphabet_cells.push("".to_string());
-                for row in &mut self.transitions {
-                    row.push("".to_string());
+    fn build_das<S: GridSymbol>(&self) -> DAS<S> {
+        let mut das = DAS::new(self.build_alphabet());
+        for (i, name) in self.state_names.iter().enumerate() {
+            let mut node = DASNode::new(name, self.accepting_states[i]);
+            node.set_position(self.state_positions[i]);
+            for (j, cell) in self.transitions[i].iter().enumerate().take(self.num_columns - 1) {
+                if !cell.is_empty() {
+                    for symbol in self.column_symbols(j) {
+                        node.add_connection(symbol, cell);
+                    }
                 }
             }
-            if ui.button("Dodaj stan").clicked() {
-                self.num_rows += 1;
-                self.state_names.push("".to_string());
-                self.transitions.push(vec!["".to_string(); self.num_columns - 1 + if matches!(self.automat_type, AutomatType::ENAS) { 1 } else { 0 }]);
-                self.accepting_states.push(false);
+            das.add_state(node);
+        }
+        if let Some(start) = self.state_names.first() {
+            das.set_start_state(start);
+        }
+        das
+    }
+
+    fn build_enas<S: GridSymbol>(&self) -> ENAS<S> {
+        let mut enas = ENAS::new(self.build_alphabet());
+        for (i, name) in self.state_names.iter().enumerate() {
+            let mut node = ENASNode::new(name, self.accepting_states[i]);
+            node.set_position(self.state_positions[i]);
+            // Przejścia dla alfabetu
+            for (j, cell) in self.transitions[i].iter().enumerate().take(self.num_columns - 1) {
+                let targets = parse_targets(cell);
+                if !targets.is_empty() {
+                    for symbol in self.column_symbols(j) {
+                        node.add_connection(Label::Symbol(symbol), targets.clone());
+                    }
+                }
             }
-            if ui.button("Usuń znak alfabetu").clicked() && self.num_columns > 2 {
-                self.num_columns -= 1;
-                self.alphabet_cells.pop();
-                for row in &mut self.transitions {
-                    row.pop();
+            // Przejście epsilon (ostatnia kolumna)
+            if let Some(cell) = self.transitions[i].get(self.num_columns - 1) {
+                let targets = parse_targets(cell);
+                if !targets.is_empty() {
+                    node.add_connection(Label::Epsilon, targets);
                 }
             }
-            if ui.button("Usuń stan").clicked() && self.num_rows > 2 {
-                self.num_rows -= 1;
-                self.state_names.pop();
-                self.transitions.pop();
-                self.accepting_states.pop();
+            enas.add_state(node);
+        }
+        if let Some(start) = self.state_names.first() {
+            enas.set_start_state(start);
+        }
+        enas
+    }
+
+    // Automat zbudowany z tabeli dla wybranego typu automatu i rodzaju symboli
+    fn automat(&self) -> Box<dyn GridAutomat> {
+        match (self.automat_type, self.symbol_mode) {
+            (AutomatType::DAS, SymbolMode::Chars) => Box::new(self.build_das::<char>()),
+            (AutomatType::DAS, SymbolMode::Tokens) => Box::new(self.build_das::<Token>()),
+            (AutomatType::ENAS, SymbolMode::Chars) => Box::new(self.build_enas::<char>()),
+            (AutomatType::ENAS, SymbolMode::Tokens) => Box::new(self.build_enas::<Token>()),
+        }
+    }
+
+    fn table_key(&self) -> TableKey {
+        TableKey {
+            alphabet_cells: self.alphabet_cells.clone(),
+            state_names: self.state_names.clone(),
+            transitions: self.transitions.clone(),
+            accepting_states: self.accepting_states.clone(),
+            state_positions: self.state_positions.clone(),
+            automat_type: self.automat_type,
+            symbol_mode: self.symbol_mode,
+            allow_partial: self.allow_partial,
+        }
+    }
+
+    // Automat z tabeli wraz z błędami i analizą osiągalności. Wszystko jest
+    // liczone od nowa tylko po zmianie tabeli, a błędy także po zmianie
+    // ciągu wejściowego; wyniki paneli są wtedy czyszczone.
+    fn refresh(&mut self) -> Rc<dyn GridAutomat> {
+        let key = self.table_key();
+        let automat = match &self.table_automat {
+            Some((cached, automat)) if *cached == key => Rc::clone(automat),
+            _ => {
+                let automat: Rc<dyn GridAutomat> = Rc::from(self.automat());
+                self.reachable = automat.reachable_states();
+                self.coreachable = automat.coreachable_states();
+                self.analysis = Analysis::default();
+                self.checked_input = None;
+                self.table_automat = Some((key, Rc::clone(&automat)));
+                automat
             }
+        };
+        if self.checked_input.as_ref() != Some(&self.input_string) {
+            self.errors = self.collect_errors(&*automat);
+            self.checked_input = Some(self.input_string.clone());
+        }
+        automat
+    }
 
-            // Wyznacz liczbę kolumn (dla ENAS +1 na epsilon)
-            let extra_epsilon = matches!(self.automat_type, AutomatType::ENAS);
-            let total_columns = self.num_columns - 1 + if extra_epsilon { 1 } else { 0 };
-
-            // Tabela stanów i przejść
-            let grid_size = egui::vec2(60.0 * (total_columns + 2) as f32, 60.0 * self.num_rows as f32);
-            ui.allocate_ui(grid_size, |ui| {
-                egui::Grid::new("my_grid")
-                    .min_col_width(60.0)
-                    .show(ui, |ui| {
-                        for row in 0..self.num_rows {
-                            for col in 0..=(total_columns + 1) {
-                                if row == 0 && col == 0 {
-                                    ui.label("Akcept.");
-                                } else if row == 0 && col == 1 {
-                                    ui.label("Stany");
-                                } else if row == 0 {
-                                    // Nagłówki alfabetu i epsilon
-                                    if col >= 2 && col < 2 + self.num_columns - 1 {
-                                        let idx = col - 2;
-                                        let cell = &mut self.alphabet_cells[idx];
-                                        if ui.text_edit_singleline(cell).changed() {
-                                            if cell.chars().count() > 1 {
-                                                let c = cell.chars().next().unwrap();
-                                                *cell = c.to_string();
-                                            }
-                                        }
-                                    } else if extra_epsilon && col == 2 + self.num_columns - 1 {
-                                        ui.label("ε");
-                                    }
-                                } else if col == 0 {
-                                    ui.checkbox(&mut self.accepting_states[row - 1], "");
-                                } else if col == 1 {
-                                    ui.text_edit_singleline(&mut self.state_names[row - 1]);
-                                } else {
-                                    // Przejścia
-                                    let tcol = col - 2;
-                                    if tcol < total_columns {
-                                        if self.transitions[row - 1].len() < total_columns {
-                                            self.transitions[row - 1].resize(total_columns, "".to_string());
-                                        }
-                                        ui.text_edit_singleline(&mut self.transitions[row - 1][tcol]);
-                                    }
-                                }
-                            }
-                            ui.end_row();
+    // Walidacja całej tabeli – wywoływana przez `refresh` po zmianie tabeli
+    fn collect_errors(&self, automat: &dyn GridAutomat) -> Vec<GridError> {
+        // Brak przejścia w kolumnie-klasie zgłaszany jest raz na komórkę, a nie dla każdego znaku
+        let mut errors: Vec<GridError> = Vec::new();
+        let mut missing_cells = HashSet::new();
+        for error in automat.errors(self.allow_partial).into_iter().map(GridError::Automat) {
+            if let GridError::Automat(ValidationError::MissingTransition { .. }) = error
+                && let Some(location) = self.locate(&error)
+                && !missing_cells.insert(location)
+            {
+                continue;
+            }
+            errors.push(error);
+        }
+
+        // Sprawdź, czy pola na symbole alfabetu są poprawne i rozłączne
+        let mut seen = HashSet::new();
+        for (i, s) in self.alphabet_cells.iter().enumerate() {
+            if s.trim().is_empty() {
+                errors.push(GridError::EmptyAlphabetCell(i));
+                continue;
+            }
+            match self.parse_column(i) {
+                Ok(symbols) => {
+                    for symbol in symbols {
+                        if !seen.insert(symbol.clone()) {
+                            errors.push(GridError::OverlappingSymbol(i, symbol));
                         }
-                    });
+                    }
+                }
+                Err(message) => errors.push(GridError::InvalidAlphabetCell(i, message)),
+            }
+        }
+
+        // Sprawdź, czy ciąg wejściowy zawiera tylko symbole z alfabetu
+        for symbol in automat.symbols_outside_alphabet(&self.input_string) {
+            errors.push(GridError::SymbolOutsideAlphabet(symbol));
+        }
+        errors
+    }
+
+    // Usuwa wiersze stanów, które nie przetrwały `trim()`, oraz odwołania do nich.
+    // Pierwszy wiersz (stan startowy) zostaje zawsze.
+    fn trim_grid(&mut self) {
+        let kept = self.automat().trimmed_states();
+        let mut removed = HashSet::new();
+        for i in (1..self.state_names.len()).rev() {
+            if !kept.contains(&self.state_names[i]) {
+                removed.insert(self.state_names.remove(i));
+                self.transitions.remove(i);
+                self.accepting_states.remove(i);
+                self.state_positions.remove(i);
+                self.num_rows -= 1;
+            }
+        }
+        for cell in self.transitions.iter_mut().flatten() {
+            let targets = parse_targets(cell);
+            if targets.iter().any(|t| removed.contains(t)) {
+                *cell = targets.into_iter().filter(|t| !removed.contains(t)).collect::<Vec<_>>().join(", ");
+            }
+        }
+    }
+
+    fn symbol_column(&self, symbol: &str) -> Option<usize> {
+        if symbol == EPSILON && self.automat_type == AutomatType::ENAS {
+            return Some(self.num_columns - 1);
+        }
+        self.alphabet_column(symbol)
+    }
+
+    fn alphabet_column(&self, symbol: &str) -> Option<usize> {
+        (0..self.alphabet_cells.len())
+            .find(|&j| self.parse_column(j).is_ok_and(|symbols| symbols.iter().any(|s| s == symbol)))
+    }
+
+    fn locate(&self, error: &GridError) -> Option<ErrorLocation> {
+        match error {
+            GridError::Automat(ValidationError::ReservedSymbol { symbol }) => {
+                self.alphabet_column(symbol).map(ErrorLocation::AlphabetCell)
+            }
+            GridError::Automat(e) => {
+                let row = self.state_names.iter().position(|s| Some(s.as_str()) == e.state())?;
+                let col = self.symbol_column(e.symbol())?;
+                Some(ErrorLocation::Transition(row, col))
+            }
+            GridError::EmptyAlphabetCell(i)
+            | GridError::InvalidAlphabetCell(i, _)
+            | GridError::OverlappingSymbol(i, _) => Some(ErrorLocation::AlphabetCell(*i)),
+            GridError::SymbolOutsideAlphabet(_) => Some(ErrorLocation::Input),
+        }
+    }
+
+    fn quick_fix(&self, error: &GridError) -> Option<QuickFix> {
+        match error {
+            GridError::Automat(ValidationError::UnknownTarget { target, .. }) => {
+                Some(QuickFix::CreateState(target.clone()))
+            }
+            GridError::Automat(ValidationError::MissingTransition { .. }) => match self.locate(error)? {
+                ErrorLocation::Transition(row, col) => Some(QuickFix::RouteToSink(row, col, self.sink_name())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn apply_quick_fix(&mut self, fix: QuickFix) {
+        match fix {
+            QuickFix::CreateState(name) => {
+                if !self.state_names.contains(&name) {
+                    self.push_state(&name);
+                }
+            }
+            QuickFix::RouteToSink(row, col, sink) => {
+                self.ensure_sink_state(&sink);
+                self.transitions[row][col] = sink;
+            }
+        }
+    }
+
+    // Nazwa ujścia dla brakujących przejść: istniejący wiersz, który już jest
+    // ujściem, albo wolna nazwa (jak w `DAS::complete`) – stan "sink" użytkownika,
+    // np. akceptujący, nie może przejąć przejść, bo zmieniłby język
+    fn sink_name(&self) -> String {
+        sink_name(|name| match self.state_names.iter().position(|s| s == name) {
+            Some(row) => self.is_sink_row(row),
+            None => true,
+        })
+    }
+
+    // Nieakceptujący stan, którego każde przejście prowadzi do niego samego
+    fn is_sink_row(&self, row: usize) -> bool {
+        let name = &self.state_names[row];
+        let columns = self.num_columns - 1;
+        !self.accepting_states[row]
+            && self.transitions[row].iter().take(columns).all(|c| c.trim() == name)
+            && self.transitions[row].iter().skip(columns).all(|c| c.trim().is_empty())
+    }
+
+    fn ensure_sink_state(&mut self, sink: &str) {
+        if !self.state_names.iter().any(|s| s == sink) {
+            self.push_state(sink);
+            // Ujście zapętla się na każdym znaku alfabetu
+            let row = self.transitions.last_mut().unwrap();
+            for cell in row.iter_mut().take(self.num_columns - 1) {
+                *cell = sink.to_string();
+            }
+        }
+    }
+
+    // Odpowiednik `DAS::complete` dla tabeli: puste komórki DAS kierowane są do ujścia
+    fn complete_with_sink(&mut self) {
+        let columns = self.num_columns - 1;
+        let has_gaps = self
+            .transitions
+            .iter()
+            .any(|row| row.iter().take(columns).any(|c| c.trim().is_empty()));
+        if !has_gaps {
+            return;
+        }
+        let sink = self.sink_name();
+        self.ensure_sink_state(&sink);
+        for row in &mut self.transitions {
+            for cell in row.iter_mut().take(columns) {
+                if cell.trim().is_empty() {
+                    *cell = sink.clone();
+                }
+            }
+        }
+    }
+
+    // Podświetla pole z błędem, dodaje podpowiedź i menu z szybkimi poprawkami
+    fn decorate(&self, ui: &egui::Ui, response: egui::Response, location: ErrorLocation, fix: &mut Option<QuickFix>) {
+        let errors: Vec<&GridError> = self
+            .errors
+            .iter()
+            .filter(|e| self.locate(e) == Some(location))
+            .collect();
+        if errors.is_empty() {
+            return;
+        }
+        ui.painter().rect_stroke(
+            response.rect.expand(1.0),
+            2.0,
+            egui::Stroke::new(1.5, egui::Color32::RED),
+        );
+        let tooltip = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("\n");
+        let response = response.on_hover_text(tooltip);
+        let fixes: Vec<QuickFix> = errors.iter().filter_map(|e| self.quick_fix(e)).collect();
+        if !fixes.is_empty() {
+            response.context_menu(|ui| {
+                for f in fixes {
+                    if ui.button(f.label()).clicked() {
+                        *fix = Some(f);
+                        ui.close_menu();
+                    }
+                }
             });
+        }
+    }
+}
 
-            ui.separator();
-            ui.horizontal(|ui| {
-                ui.label("Ciąg wejściowy:");
-                ui.text_edit_singleline(&mut self.input_string);
-                if ui.button("Sprawdź").clicked() {
-                    self.result = None;
-                    self.validation_message.clear();
+impl MyApp {
+    // Poprawny automat na znakach – warunek eksportu i dodania do leksera
+    fn has_valid_char_automat(&self) -> bool {
+        self.errors.is_empty() && self.symbol_mode == SymbolMode::Chars
+    }
 
-                    match self.automat_type {
-                        AutomatType::DAS => {
+    // Bieżący automat na znakach – źródło wszystkich eksportów
+    fn char_automat(&self) -> Automat {
+        match self.automat_type {
+            AutomatType::DAS => Automat::DAS(self.build_das::<char>()),
+            AutomatType::ENAS => Automat::ENAS(self.build_enas::<char>()),
+        }
+    }
 
-                            // 1. Budowa alfabetu
-                            let mut alphabet = Alphabet::new();
-                            for s in &self.alphabet_cells {
-                                if let Some(c) = s.chars().next() {
-                                    alphabet.add(c);
-                                }
-                            }
+    // Zapis tekstowy odzwierciedlający tabelę
+    fn sync_text(&mut self) {
+        if self.has_valid_char_automat() {
+            self.text_editor.set_automat(&self.char_automat().to_enas());
+        } else {
+            self.text_editor.set_outdated("Tabela zawiera błędy albo jest w trybie tokenów – tekst jej nie odzwierciedla.");
+        }
+    }
 
-                            // 2. Budowa stanów
-                            let mut nodes = vec![];
-                            for (i, name) in self.state_names.iter().enumerate() {
-                                let mut node = DASNode::new(name, self.accepting_states[i]);
-                                for (j, cell) in self.transitions[i].iter().enumerate().take(self.num_columns - 1) {
-                                    if !cell.is_empty() {
-                                        if let Some(symbol) = self.alphabet_cells[j].chars().next() {
-                                            node.add_connection(symbol, cell);
-                                        }
-                                    }
-                                }
-                                nodes.push(node);
-                            }
+    // Automat z zakładki tekstowej; stany o tych samych nazwach zachowują położenia
+    fn load_text_automat(&mut self, automat: Automat) -> Result<(), String> {
+        let positions: HashMap<String, (f32, f32)> = self
+            .state_names
+            .iter()
+            .zip(&self.state_positions)
+            .filter_map(|(name, position)| position.map(|p| (name.clone(), p)))
+            .collect();
+        self.load_automat(automat)?;
+        self.state_positions = self.state_names.iter().map(|name| positions.get(name).copied()).collect();
+        self.text_outdated = false;
+        Ok(())
+    }
 
-                            // 3. Budowa DAS
-                            let mut das = DAS::new(alphabet);
-                            for node in nodes {
-                                das.add_state(node);
-                            }
-                            if let Some(start) = self.state_names.get(0) {
-                                das.set_start_state(start);
-                            }
+    // Generatory kodu obsługują tylko DAS – ε-NAS jest najpierw determinizowany
+    fn export_code(&mut self, backend: Backend) {
+        let das = match self.char_automat() {
+            Automat::DAS(das) => das,
+            Automat::ENAS(enas) => enas.determinize().renumbered(),
+        };
+        self.export_window = Some(ExportWindow::new(
+            &format!("Kod: {}", backend.label()),
+            backend.generate(&das),
+            &format!("automat.{}", backend.extension()),
+        ));
+    }
 
-                            // --- WALIDACJA DAS ---
-                            let mut errors = das.validate();
+    fn export_jflap(&mut self) {
+        let xml = Jflap::write(&self.char_automat().to_enas());
+        self.export_window = Some(ExportWindow::new("JFLAP", Ok(xml), "automat.jff"));
+    }
 
-                            // Sprawdź, czy pola na znaki alfabetu nie są puste
-                            for (i, s) in self.alphabet_cells.iter().enumerate() {
-                                if s.trim().is_empty() {
-                                    errors.push(format!("Pole na znak alfabetu w kolumnie {} jest puste.", i + 1));
-                                }
-                            }
+    fn export_tikz(&mut self) {
+        let tex = Tikz::write(&self.char_automat().to_enas());
+        self.export_window = Some(ExportWindow::new("TikZ", Ok(tex), "automat.tex"));
+    }
 
-                            // Sprawdź, czy ciąg wejściowy zawiera tylko znaki z alfabetu
-                            for c in self.input_string.chars() {
-                                if !das.alphabet.contains(&c) {
-                                    errors.push(format!(
-                                        "Ciąg wejściowy zawiera znak '{}' spoza alfabetu.",
-                                        c
-                                    ));
-                                }
-                            }
+    // Plik OpenFst i jego tabele symboli eksportowane są osobno
+    fn export_att(&mut self, part: usize) {
+        let files = Att::write(&self.char_automat().to_enas());
+        let (title, text, file_name) = match part {
+            0 => ("OpenFst: automat", files.fst, "automat.fst.txt"),
+            1 => ("OpenFst: symbole wejściowe", files.isymbols, "automat.isyms"),
+            _ => ("OpenFst: nazwy stanów", files.ssymbols, "automat.ssyms"),
+        };
+        self.export_window = Some(ExportWindow::new(title, Ok(text), file_name));
+    }
 
-                            if !errors.is_empty() {
-                                self.result = None;
-                                self.validation_message = errors.join("\n");
-                            } else {
-                                // 4. Sprawdzenie ciągu
-                                self.result = Some(das.process(&self.input_string));
-                                self.validation_message.clear();
+    fn export_mermaid(&mut self) {
+        let text = Mermaid::write(&self.char_automat().to_enas());
+        self.export_window = Some(ExportWindow::new("Mermaid", Ok(text), "automat.mmd"));
+    }
+
+    fn export_plantuml(&mut self) {
+        let text = PlantUml::write(&self.char_automat().to_enas());
+        self.export_window = Some(ExportWindow::new("PlantUML", Ok(text), "automat.puml"));
+    }
+
+    fn export_image(&mut self, png: bool) {
+        let svg = Svg::render(&self.char_automat().to_enas(), self.image_layout);
+        self.export_window = Some(if png {
+            ExportWindow::binary("Obraz PNG", Png::render(&svg, 2.0), "automat.png")
+        } else {
+            ExportWindow::new("Obraz SVG", Ok(svg), "automat.svg")
+        });
+    }
+
+    // Tabela w postaci do eksportu (CSV, Markdown, LaTeX); pierwszy wiersz jest startowy
+    fn grid_table(&self) -> TransitionTable {
+        let epsilon = self.automat_type == AutomatType::ENAS;
+        let width = self.num_columns - 1 + usize::from(epsilon);
+        let rows = self
+            .state_names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let mut cells: Vec<String> = self.transitions[i].iter().take(width).map(|c| c.trim().to_string()).collect();
+                cells.resize(width, String::new());
+                TableRow {
+                    state: name.clone(),
+                    start: i == 0,
+                    accepting: self.accepting_states[i],
+                    cells,
+                }
+            })
+            .collect();
+        TransitionTable {
+            symbols: self.alphabet_cells.clone(),
+            epsilon,
+            rows,
+        }
+    }
+
+    // Zastępuje tabelę wczytaną (stan startowy trafia do pierwszego wiersza);
+    // niepełny DAS włącza "Dopuszczaj niepełny DAS"
+    fn load_table(&mut self, mut table: TransitionTable, automat_type: AutomatType) -> Result<(), String> {
+        check_table_size(table.rows.len(), table.symbols.len())?;
+        table.rows.sort_by_key(|row| !row.start);
+        self.automat_type = automat_type;
+        self.num_columns = table.symbols.len() + 1;
+        self.num_rows = table.rows.len() + 1;
+        self.alphabet_cells = table.symbols;
+        self.state_names = table.rows.iter().map(|r| r.state.clone()).collect();
+        self.accepting_states = table.rows.iter().map(|r| r.accepting).collect();
+        self.state_positions = vec![None; table.rows.len()];
+        let width = self.num_columns - 1 + usize::from(automat_type == AutomatType::ENAS);
+        self.transitions = table
+            .rows
+            .into_iter()
+            .map(|row| {
+                let mut cells = row.cells;
+                if automat_type == AutomatType::DAS && table.epsilon {
+                    cells.pop();
+                }
+                cells.resize(width, String::new());
+                cells
+            })
+            .collect();
+        if automat_type == AutomatType::DAS {
+            let columns = self.num_columns - 1;
+            self.allow_partial = self.transitions.iter().any(|row| row.iter().take(columns).any(|c| c.is_empty()));
+        }
+        self.result = None;
+        self.text_outdated = true;
+        Ok(())
+    }
+
+    // Wczytany automat jako tabela: kolumna na symbol, wiersz na stan
+    fn load_automat(&mut self, automat: Automat) -> Result<(), String> {
+        let automat_type = match automat {
+            Automat::DAS(_) => AutomatType::DAS,
+            Automat::ENAS(_) => AutomatType::ENAS,
+        };
+        let enas = automat.to_enas();
+        let symbols = enas.alphabet().symbols();
+        let states = ordered_states(enas.get_start_state(), enas.get_states().keys());
+        let mut labels: Vec<Label> = symbols.iter().map(|&c| Label::Symbol(c)).collect();
+        labels.push(Label::Epsilon);
+        let nodes: Vec<&ENASNode> = states.iter().map(|s| &enas.get_states()[s]).collect();
+        let table = TransitionTable {
+            // Białe znaki zapisywane jako klasa – pojedyncza spacja zostałaby obcięta
+            symbols: symbols
+                .iter()
+                .map(|c| if c.is_whitespace() { format!("[{}]", c) } else { c.to_string() })
+                .collect(),
+            epsilon: true,
+            rows: nodes
+                .iter()
+                .map(|n| TableRow {
+                    state: n.get_name().to_string(),
+                    start: n.get_name() == enas.get_start_state(),
+                    accepting: n.is_accepting(),
+                    cells: labels
+                        .iter()
+                        .map(|l| n.get_connections().get(l).map(|t| t.join(", ")).unwrap_or_default())
+                        .collect(),
+                })
+                .collect(),
+        };
+        self.load_table(table, automat_type)?;
+        self.symbol_mode = SymbolMode::Chars;
+        self.state_positions = nodes.iter().map(|n| n.get_position()).collect();
+        Ok(())
+    }
+
+    // `extra` – zawartość plików dodatkowych z `ImportFormat::extra_files`
+    fn import(&mut self, format: ImportFormat, text: &str, extra: &[Option<String>]) -> Result<(), String> {
+        match format {
+            ImportFormat::Jflap => self.load_automat(Jflap::read(text)?)?,
+            ImportFormat::Att => {
+                let table = |i: usize| extra.get(i).and_then(|t| t.as_deref());
+                self.load_automat(Att::read(text, table(0), table(1))?)?;
+            }
+            ImportFormat::Csv | ImportFormat::Tsv => {
+                let delimiter = if format == ImportFormat::Tsv { '\t' } else { csv_delimiter(text) };
+                let table = TransitionTable::parse(text, delimiter)?;
+                let automat_type = table_type(&table);
+                self.load_table(table, automat_type)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Ctrl+V poza polem tekstowym: blok TSV skopiowany z arkusza zastępuje tabelę
+    fn handle_paste(&mut self, ctx: &egui::Context) {
+        if ctx.memory(|m| m.focused().is_some()) {
+            return;
+        }
+        let pasted = ctx.input(|i| {
+            i.events.iter().find_map(|e| match e {
+                egui::Event::Paste(text) if text.contains('\t') => Some(text.clone()),
+                _ => None,
+            })
+        });
+        if let Some(text) = pasted {
+            self.paste_error = self.import(ImportFormat::Tsv, &text, &[]).err();
+        }
+    }
+
+    fn menu_bar(&mut self, ui: &mut egui::Ui) {
+        egui::menu::bar(ui, |ui| {
+            ui.menu_button("Plik", |ui| {
+                if ui.button("Nowy").on_hover_text("Zastępuje tabelę jednym stanem q0 i symbolem a").clicked() {
+                    self.new_automat();
+                    ui.close_menu();
+                }
+                ui.add_enabled_ui(!self.recent_files.is_empty(), |ui| {
+                    ui.menu_button("Ostatnie pliki", |ui| {
+                        let mut opened = None;
+                        for file in &self.recent_files {
+                            if ui.button(format!("{} – {}", file.path, file.format.label())).clicked() {
+                                opened = Some(file.clone());
                             }
                         }
-                        AutomatType::ENAS => {
-                            // 1. Budowa alfabetu (bez epsilon)
-                            let mut alphabet = Alphabet::new();
-                            for s in &self.alphabet_cells {
-                                if let Some(c) = s.chars().next() {
-                                    alphabet.add(c);
-                                }
+                        ui.separator();
+                        if ui.button("Wyczyść listę").clicked() {
+                            self.recent_files.clear();
+                            ui.close_menu();
+                        }
+                        if let Some(file) = opened {
+                            self.open_recent(file);
+                            ui.close_menu();
+                        }
+                    });
+                });
+            });
+            ui.menu_button("Import", |ui| {
+                for format in ImportFormat::ALL {
+                    if ui.button(format!("{}…", format.label())).clicked() {
+                        self.import_window = Some((format, Self::import_window(format, format.file_name(), &[])));
+                        ui.close_menu();
+                    }
+                }
+            });
+            ui.menu_button("Eksport", |ui| {
+                let enabled = self.has_valid_char_automat();
+                ui.add_enabled_ui(enabled, |ui| {
+                    ui.menu_button("Kod źródłowy", |ui| {
+                        for backend in Backend::ALL {
+                            if ui.selectable_label(backend == self.code_language, backend.label()).clicked() {
+                                self.code_language = backend;
+                                self.export_code(backend);
+                                ui.close_menu();
                             }
-
-                            // 2. Budowa stanów ENAS
-                            let mut nodes = vec![];
-                            for (i, name) in self.state_names.iter().enumerate() {
-                                let mut node = ENASNode::new(name, self.accepting_states[i]);
-                                // Przejścia dla alfabetu
-                                for (j, cell) in self.transitions[i].iter().enumerate().take(self.num_columns - 1) {
-                                    if !cell.trim().is_empty() {
-                                        if let Some(symbol) = self.alphabet_cells[j].chars().next() {
-                                            let targets: Vec<String> = cell
-                                                .split(',')
-                                                .map(|s| s.trim().to_string())
-                                                .filter(|s| !s.is_empty())
-                                                .collect();
-                                            if !targets.is_empty() {
-                                                node.add_connection(symbol, targets);
-                                            }
-                                        }
-                                    }
-                                }
-                                // Przejście epsilon (ostatnia kolumna)
-                                if self.transitions[i].len() > self.num_columns - 1 {
-                                    let cell = &self.transitions[i][self.num_columns - 1];
-                                    if !cell.trim().is_empty() {
-                                        let targets: Vec<String> = cell
-                                            .split(',')
-                                            .map(|s| s.trim().to_string())
-                                            .filter(|s| !s.is_empty())
-                                            .collect();
-                                        if !targets.is_empty() {
-                                            node.add_connection('ε', targets);
-                                        }
-                                    }
-                                }
-                                nodes.push(node);
+                        }
+                    });
+                    if ui.button("JFLAP (.jff)").clicked() {
+                        self.export_jflap();
+                        ui.close_menu();
+                    }
+                    if ui.button("Diagram TikZ (LaTeX)").clicked() {
+                        self.export_tikz();
+                        ui.close_menu();
+                    }
+                    ui.menu_button("OpenFst (AT&T)", |ui| {
+                        let parts = ["Automat (.fst.txt)", "Symbole wejściowe (.isyms)", "Nazwy stanów (.ssyms)"];
+                        for (part, label) in parts.into_iter().enumerate() {
+                            if ui.button(label).clicked() {
+                                self.export_att(part);
+                                ui.close_menu();
                             }
+                        }
+                    });
+                    if ui.button("Mermaid (stateDiagram-v2)").clicked() {
+                        self.export_mermaid();
+                        ui.close_menu();
+                    }
+                    if ui.button("PlantUML").clicked() {
+                        self.export_plantuml();
+                        ui.close_menu();
+                    }
+                    ui.menu_button("Obraz diagramu", |ui| {
+                        ui.label("Układ stanów:");
+                        ui.radio_value(&mut self.image_layout, LayoutKind::Stored, "Zapisany")
+                            .on_hover_text("Położenia z importu; pozostałe stany warstwowo");
+                        ui.radio_value(&mut self.image_layout, LayoutKind::Layered, "Warstwowy");
+                        ui.radio_value(&mut self.image_layout, LayoutKind::Force, "Siłowy");
+                        ui.separator();
+                        if ui.button("SVG").clicked() {
+                            self.export_image(false);
+                            ui.close_menu();
+                        }
+                        if ui.button("PNG").clicked() {
+                            self.export_image(true);
+                            ui.close_menu();
+                        }
+                    });
+                })
+                .response
+                .on_disabled_hover_text("Wymaga poprawnego automatu w trybie znaków");
+                // Tabela eksportowana jest tak, jak wygląda – także z błędami
+                ui.menu_button("Tabela przejść", |ui| {
+                    let table = self.grid_table();
+                    let exports = [
+                        ("CSV", "automat.csv", table.to_delimited(',')),
+                        ("TSV", "automat.tsv", table.to_delimited('\t')),
+                        ("Markdown", "automat.md", table.to_markdown()),
+                        ("LaTeX", "automat.tex", table.to_latex()),
+                    ];
+                    for (label, file_name, text) in exports {
+                        if ui.button(label).clicked() {
+                            self.export_window = Some(ExportWindow::new(&format!("Tabela: {}", label), Ok(text), file_name));
+                            ui.close_menu();
+                        }
+                    }
+                });
+            });
+        });
+    }
+}
 
-                            // 3. Budowa ENAS
-                            let mut enas = ENAS::new(alphabet);
-                            for node in nodes {
-                                enas.add_state(node);
-                            }
-                            if let Some(start) = self.state_names.get(0) {
-                                enas.set_start_state(start);
-                            }
+impl eframe::App for MyApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, &self.session());
+    }
 
-                            // --- WALIDACJA ENAS ---
-                            let mut errors = enas.validate();
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.handle_paste(ctx);
+        let automat = self.refresh();
+        if self.editor_view == EditorView::Text && self.text_outdated {
+            self.sync_text();
+            self.text_outdated = false;
+        }
+        let mut fix: Option<QuickFix> = None;
+        let mut text_automat = None;
 
-                            // Sprawdź, czy pola na znaki alfabetu nie są puste
-                            for (i, s) in self.alphabet_cells.iter().enumerate() {
-                                if s.trim().is_empty() {
-                                    errors.push(format!("Pole na znak alfabetu w kolumnie {} jest puste.", i + 1));
-                                }
-                            }
+        egui::TopBottomPanel::top("menu").show(ctx, |ui| self.menu_bar(ui));
+        egui::CentralPanel::default().show(ctx, |ui| {
+            // Przełącznik typu automatu
+            ui.horizontal(|ui| {
+                ui.label("Typ automatu:");
+                ui.radio_value(&mut self.automat_type, AutomatType::DAS, "DAS");
+                ui.radio_value(&mut self.automat_type, AutomatType::ENAS, "ε-NAS");
+                if self.automat_type == AutomatType::ENAS {
+                    ui.checkbox(&mut self.text_editing, "Edycja tekstowa przejść");
+                } else {
+                    ui.checkbox(&mut self.allow_partial, "Dopuszczaj niepełny DAS")
+                        .on_hover_text("Brak przejścia oznacza odrzucenie słowa");
+                    if ui.button("Uzupełnij ujściem").clicked() {
+                        self.complete_with_sink();
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Symbole:");
+                ui.radio_value(&mut self.symbol_mode, SymbolMode::Chars, "Znaki");
+                ui.radio_value(&mut self.symbol_mode, SymbolMode::Tokens, "Tokeny")
+                    .on_hover_text("Ciąg wejściowy to tokeny oddzielone spacjami");
+            });
 
-                            // Sprawdź, czy ciąg wejściowy zawiera tylko znaki z alfabetu
-                            for c in self.input_string.chars() {
-                                if !enas.alphabet.contains(&c) {
-                                    errors.push(format!(
-                                        "Ciąg wejściowy zawiera znak '{}' spoza alfabetu.",
-                                        c
-                                    ));
+            ui.horizontal(|ui| {
+                ui.label("Edytor:");
+                ui.selectable_value(&mut self.editor_view, EditorView::Table, "Tabela");
+                if ui.selectable_value(&mut self.editor_view, EditorView::Text, "Tekst").clicked() {
+                    self.text_outdated = true;
+                }
+            });
+
+            if self.editor_view == EditorView::Text {
+                text_automat = self.text_editor.show(ui);
+            } else {
+                // Przyciski do edycji tabeli
+                if ui.button("Dodaj symbol alfabetu").clicked() {
+                    self.num_columns += 1;
+                    self.alphabet_cells.push("".to_string());
+                    for row in &mut self.transitions {
+                        row.push("".to_string());
+                    }
+                }
+                if ui.button("Dodaj stan").clicked() {
+                    self.push_state("");
+                }
+                if ui.button("Usuń symbol alfabetu").clicked() && self.num_columns > 2 {
+                    self.num_columns -= 1;
+                    self.alphabet_cells.pop();
+                    for row in &mut self.transitions {
+                        row.pop();
+                    }
+                }
+                if ui.button("Usuń stan").clicked() && self.num_rows > 2 {
+                    self.num_rows -= 1;
+                    self.state_names.pop();
+                    self.transitions.pop();
+                    self.accepting_states.pop();
+                    self.state_positions.pop();
+                }
+                if ui.button("Usuń zbędne stany").on_hover_text("Usuwa stany nieosiągalne i martwe").clicked() {
+                    self.trim_grid();
+                }
+                ui.label("Ctrl+V poza polem tekstowym wkleja tabelę skopiowaną z arkusza (TSV).")
+                    .on_hover_text("Nagłówek: symbole alfabetu (i ε); wiersze: ->q0 – stan startowy, *q1 – akceptujący");
+                if let Some(message) = &self.paste_error {
+                    ui.colored_label(egui::Color32::RED, message);
+                }
+
+                // Wyznacz liczbę kolumn (dla ENAS +1 na epsilon)
+                let extra_epsilon = matches!(self.automat_type, AutomatType::ENAS);
+                let total_columns = self.num_columns - 1 + if extra_epsilon { 1 } else { 0 };
+
+                // Tabela stanów i przejść
+                let grid_size = egui::vec2(60.0 * (total_columns + 2) as f32, 60.0 * self.num_rows as f32);
+                ui.allocate_ui(grid_size, |ui| {
+                    egui::Grid::new("my_grid")
+                        .min_col_width(60.0)
+                        .show(ui, |ui| {
+                            for row in 0..self.num_rows {
+                                for col in 0..=(total_columns + 1) {
+                                    if row == 0 && col == 0 {
+                                        ui.label("Akcept.");
+                                    } else if row == 0 && col == 1 {
+                                        ui.label("Stany");
+                                    } else if row == 0 {
+                                        // Nagłówki alfabetu i epsilon
+                                        if col >= 2 && col < 2 + self.num_columns - 1 {
+                                            let idx = col - 2;
+                                            let cell = &mut self.alphabet_cells[idx];
+                                            let hint = match self.symbol_mode {
+                                                SymbolMode::Chars => "Znak, zakres (a-z) lub klasa ([0-9])",
+                                                SymbolMode::Tokens => "Pojedynczy token (bez spacji)",
+                                            };
+                                            let response = ui.text_edit_singleline(cell).on_hover_text(hint);
+                                            self.decorate(ui, response, ErrorLocation::AlphabetCell(idx), &mut fix);
+                                        } else if extra_epsilon && col == 2 + self.num_columns - 1 {
+                                            ui.label("ε");
+                                        }
+                                    } else if col == 0 {
+                                        ui.checkbox(&mut self.accepting_states[row - 1], "");
+                                    } else if col == 1 {
+                                        // Stany nieosiągalne są wyszarzone, martwe – pomarańczowe
+                                        let name = &self.state_names[row - 1];
+                                        let mut notes = Vec::new();
+                                        let mut color = None;
+                                        if !self.coreachable.contains(name) {
+                                            notes.push("Stan martwy: nie prowadzi do żadnego stanu akceptującego.");
+                                            color = Some(egui::Color32::from_rgb(230, 140, 0));
+                                        }
+                                        if !self.reachable.contains(name) {
+                                            notes.push("Stan nieosiągalny ze stanu startowego.");
+                                            color = Some(egui::Color32::GRAY);
+                                        }
+                                        let edit = egui::TextEdit::singleline(&mut self.state_names[row - 1]).text_color_opt(color);
+                                        let response = ui.add(edit);
+                                        if !notes.is_empty() {
+                                            response.on_hover_text(notes.join("\n"));
+                                        }
+                                    } else {
+                                        // Przejścia
+                                        let tcol = col - 2;
+                                        if tcol < total_columns {
+                                            if self.transitions[row - 1].len() < total_columns {
+                                                self.transitions[row - 1].resize(total_columns, "".to_string());
+                                            }
+                                            let cell = &mut self.transitions[row - 1][tcol];
+                                            let response = match self.automat_type {
+                                                AutomatType::DAS => target_combo(ui, cell, &self.state_names),
+                                                AutomatType::ENAS if self.text_editing => ui.text_edit_singleline(cell),
+                                                AutomatType::ENAS => target_chips(ui, cell, &self.state_names),
+                                            };
+                                            self.decorate(ui, response, ErrorLocation::Transition(row - 1, tcol), &mut fix);
+                                        }
+                                    }
                                 }
+                                ui.end_row();
                             }
+                        });
+                });
+            }
 
-                            if !errors.is_empty() {
-                                self.result = None;
-                                self.validation_message = errors.join("\n");
-                            } else {
-                                // 4. Sprawdzenie ciągu
-                                self.result = Some(enas.process(&self.input_string));
-                                self.validation_message.clear();
-                            }
-                        }
-                    }
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Ciąg wejściowy:");
+                let mut response = ui.text_edit_singleline(&mut self.input_string);
+                if self.symbol_mode == SymbolMode::Tokens {
+                    response = response.on_hover_text("Tokeny oddzielone spacjami");
+                }
+                self.decorate(ui, response, ErrorLocation::Input, &mut fix);
+                if ui.button("Sprawdź").clicked() {
+                    self.result = if !self.errors.is_empty() {
+                        None
+                    } else {
+                        Some(automat.accepts_input(&self.input_string))
+                    };
                 }
             });
 
-            // Wyświetlanie komunikatów walidacji lub wyniku
-            if !self.validation_message.is_empty() {
-                for line in self.validation_message.lines() {
-                    ui.colored_label(egui::Color32::RED, line);
+            // Lista błędów z szybkimi poprawkami lub wynik
+            if !self.errors.is_empty() {
+                for error in &self.errors {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(egui::Color32::RED, error.to_string());
+                        if let Some(f) = self.quick_fix(error)
+                            && ui.small_button(f.label()).clicked()
+                        {
+                            fix = Some(f);
+                        }
+                    });
                 }
             } else if let Some(result) = self.result {
                 if result {
@@ -304,15 +1286,198 @@ impl eframe::App for MyApp {
                     ui.colored_label(egui::Color32::RED, "Ciąg odrzucony");
                 }
             }
+
+            // Własności języka liczone tylko dla poprawnego automatu i rozwiniętego panelu
+            if self.errors.is_empty() {
+                egui::CollapsingHeader::new("Własności języka").show(ui, |ui| {
+                    for (name, holds, witness) in self.analysis.properties(&*automat) {
+                        let answer = if *holds { "tak" } else { "nie" };
+                        match witness {
+                            Some(witness) => ui.label(format!("{}: {} – {}", name, answer, witness)),
+                            None => ui.label(format!("{}: {}", name, answer)),
+                        };
+                    }
+                });
+                egui::CollapsingHeader::new("Przykładowe słowa").show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Liczba słów:");
+                        ui.add(egui::DragValue::new(&mut self.sample_count).clamp_range(1..=100));
+                        ui.label("Maks. długość:");
+                        ui.add(egui::DragValue::new(&mut self.sample_max_len).clamp_range(0..=20));
+                    });
+                    let (accepted, rejected) = self.analysis.samples(&*automat, self.sample_count, self.sample_max_len);
+                    ui.colored_label(egui::Color32::GREEN, format!("Akceptowane: {}", accepted.join(", ")));
+                    ui.colored_label(egui::Color32::RED, format!("Odrzucane: {}", rejected.join(", ")));
+                });
+                egui::CollapsingHeader::new("Liczba słów").show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Maks. długość:");
+                        ui.add(egui::DragValue::new(&mut self.count_max_len).clamp_range(0..=200));
+                    });
+                    let (counts, recurrence, generating_function) = self.analysis.counts(&*automat, self.count_max_len);
+                    for (n, count) in counts.iter().enumerate() {
+                        ui.label(format!("n = {}: {}", n, count));
+                    }
+                    ui.label(format!("Rekurencja: {}", recurrence));
+                    ui.label(format!("Funkcja tworząca: {}", generating_function));
+                });
+                egui::CollapsingHeader::new("Wyszukiwanie w tekście").show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Dopasowania:");
+                        ui.radio_value(&mut self.match_kind, MatchKind::LeftmostLongest, "Najdłuższe");
+                        ui.radio_value(&mut self.match_kind, MatchKind::LeftmostFirst, "Pierwsze (jak w PCRE)");
+                    });
+                    let kind = self.match_kind;
+                    let analysis = &mut self.analysis;
+                    highlighted_text(ui, &mut self.search_text, |text| analysis.matches(&*automat, text, kind).to_vec());
+                    let count = analysis.matches(&*automat, &self.search_text, kind).len();
+                    ui.label(format!("Liczba dopasowań: {}", count));
+                });
+            }
+            egui::CollapsingHeader::new("Lekser").show(ui, |ui| {
+                let can_add = self.has_valid_char_automat();
+                if self.lexer_panel.show(ui, can_add) {
+                    let enas = match self.automat_type {
+                        AutomatType::DAS => self.build_das::<char>().to_enas(),
+                        AutomatType::ENAS => self.build_enas::<char>(),
+                    };
+                    self.lexer_panel.add_automat(enas);
+                }
+            });
         });
+
+        if let Some(window) = &mut self.export_window
+            && !window.show(ctx)
+        {
+            self.export_window = None;
+        }
+        if let Some((format, window)) = &mut self.import_window {
+            let format = *format;
+            match window.show(ctx) {
+                ImportAction::None => {}
+                ImportAction::Close => self.import_window = None,
+                ImportAction::Read(text, extra) => match self.import(format, &text, &extra) {
+                    Ok(()) => {
+                        if let Some((_, window)) = self.import_window.take() {
+                            let (path, extra) = window.paths();
+                            self.remember_file(format, path, extra);
+                        }
+                    }
+                    Err(message) => {
+                        if let Some((_, window)) = &mut self.import_window {
+                            window.set_error(message);
+                        }
+                    }
+                },
+            }
+        }
+
+        if let Some(fix) = fix {
+            self.apply_quick_fix(fix);
+        }
+        if let Some(automat) = text_automat
+            && let Err(message) = self.load_text_automat(automat)
+        {
+            self.text_editor.set_error(message);
+        }
     }
 }
 
 fn main() -> eframe::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(message) = cli::run(&args) {
+            eprintln!("{}", message);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
     let options = eframe::NativeOptions::default();
     eframe::run_native(
         "Maszyna Stanów (egui)",
         options,
-        Box::new(|_cc| Box::new(MyApp::default())),
+        Box::new(|cc| Box::new(MyApp::new(cc))),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Tabela DAS nad {a, b} z akceptującym stanem "sink" użytkownika
+    fn app_with_user_sink() -> MyApp {
+        let mut app = MyApp::default();
+        app.import(ImportFormat::Csv, ",a,b\n->q0,sink,\n*sink,sink,sink\n", &[]).unwrap();
+        app
+    }
+
+    #[test]
+    fn complete_with_sink_keeps_user_sink() {
+        let mut app = app_with_user_sink();
+        app.complete_with_sink();
+        assert_eq!(app.state_names, ["q0", "sink", "sink1"]);
+        assert_eq!(app.transitions[0], ["sink", "sink1"]);
+        assert_eq!(app.transitions[2], ["sink1", "sink1"]);
+        let das = app.build_das::<char>();
+        assert!(!das.accepts("b".chars()));
+        assert!(das.accepts("ab".chars()));
+    }
+
+    #[test]
+    fn route_to_sink_reuses_created_sink() {
+        let mut app = app_with_user_sink();
+        app.transitions[0][0].clear();
+        for col in [0, 1] {
+            let fix = QuickFix::RouteToSink(0, col, app.sink_name());
+            app.apply_quick_fix(fix);
+        }
+        assert_eq!(app.state_names, ["q0", "sink", "sink1"]);
+        assert_eq!(app.transitions[0], ["sink1", "sink1"]);
+    }
+
+    #[test]
+    fn refresh_rebuilds_only_after_changes() {
+        let mut app = app_with_user_sink();
+        let automat = app.refresh();
+        assert!(Rc::ptr_eq(&automat, &app.refresh()));
+        assert!(app.errors.is_empty());
+
+        // Zmiana ciągu wejściowego odświeża tylko błędy
+        app.input_string = "abc".to_string();
+        assert!(Rc::ptr_eq(&automat, &app.refresh()));
+        assert_eq!(app.errors.len(), 1);
+
+        app.accepting_states[0] = true;
+        let changed = app.refresh();
+        assert!(!Rc::ptr_eq(&automat, &changed));
+        assert!(changed.accepts_input(""));
+    }
+
+    #[test]
+    fn import_rejects_table_without_states() {
+        let mut app = app_with_user_sink();
+        assert!(app.import(ImportFormat::Csv, ",a,b\n", &[]).is_err());
+        assert!(app.import(ImportFormat::Tsv, "", &[]).is_err());
+        let empty = Automat::ENAS(ENAS::new(Alphabet::new()));
+        assert_eq!(app.load_automat(empty), Err("Tabela nie zawiera żadnego stanu".to_string()));
+        assert_eq!(app.state_names, ["q0", "sink"]);
+        assert_eq!(app.num_rows, 3);
+    }
+
+    #[test]
+    fn restore_rejects_table_without_states() {
+        let mut app = app_with_user_sink();
+        let mut session = app.session();
+        session.state_names.clear();
+        session.transitions.clear();
+        session.accepting_states.clear();
+        session.state_positions.clear();
+        app.restore(session);
+        assert_eq!(app.state_names, ["q0", "sink"]);
+
+        let mut session = app.session();
+        session.alphabet_cells.clear();
+        app.restore(session);
+        assert_eq!(app.alphabet_cells, ["a", "b"]);
+    }
+}
@@ -0,0 +1,226 @@
+use rust_state_machine::automats::DAS::DAS;
+use rust_state_machine::automats::ENAS::ENAS;
+use rust_state_machine::codegen::Backend;
+use rust_state_machine::elements::Alphabet::Alphabet;
+use rust_state_machine::elements::Node::DASNode;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+const RUST_HARNESS: &str = "
+#[allow(dead_code)]
+mod generated;
+
+use std::io::BufRead;
+
+fn main() {
+    for line in std::io::stdin().lock().lines() {
+        println!(\"{}\", generated::matches(&line.unwrap()) as u8);
+    }
+}
+";
+
+const C_HARNESS: &str = "
+#include <stdio.h>
+#include <string.h>
+#include \"generated.c\"
+
+int main(void) {
+    char line[4096];
+    while (fgets(line, sizeof line, stdin)) {
+        line[strcspn(line, \"\\n\")] = 0;
+        printf(\"%d\\n\", automat_matches(line));
+    }
+    return 0;
+}
+";
+
+const JS_HARNESS: &str = "
+import { matches } from './generated.mjs';
+import { readFileSync } from 'node:fs';
+
+const lines = readFileSync(0, 'utf8').split('\\n');
+lines.pop();
+for (const line of lines) {
+    console.log(matches(line) ? '1' : '0');
+}
+";
+
+// (nazwa, czy akceptujący, przejścia)
+type StateSpec<'a> = (&'a str, bool, &'a [(char, &'a str)]);
+
+fn build_das(symbols: &[char], states: &[StateSpec], start: &str) -> DAS {
+    let mut alphabet = Alphabet::new();
+    for &c in symbols {
+        alphabet.add(c);
+    }
+    let mut das = DAS::new(alphabet);
+    for &(name, accepting, transitions) in states {
+        let mut node = DASNode::new(name, accepting);
+        for &(c, target) in transitions {
+            node.add_connection(c, target);
+        }
+        das.add_state(node);
+    }
+    das.set_start_state(start);
+    das
+}
+
+// Słowa do porównania: pierwsze akceptowane i odrzucane w porządku shortlex
+// oraz słowa ze znakami spoza alfabetu
+fn sample_words(das: &DAS) -> Vec<String> {
+    let mut words: Vec<String> = das
+        .accepted_words(6)
+        .take(150)
+        .chain(das.rejected_words(6).take(150))
+        .map(|w| w.into_iter().collect())
+        .collect();
+    for word in words.clone().iter().take(20) {
+        words.push(format!("{}#", word));
+        words.push(format!("#{}", word));
+    }
+    words
+}
+
+// Czy narzędzie jest dostępne w PATH; testy C i JavaScriptu są pomijane bez
+// kompilatora C lub Node.js
+fn available(tool: &str) -> bool {
+    Command::new(tool).arg("--version").output().is_ok_and(|o| o.status.success())
+}
+
+// Kompiluje (rustc, cc) lub uruchamia (node) wygenerowany kod i zwraca
+// wyniki `matches` dla słów; None, gdy brak narzędzia
+fn run_generated(backend: Backend, dir: &Path, code: &str, words: &[String]) -> Option<Vec<bool>> {
+    std::fs::create_dir_all(dir).unwrap();
+    let binary = dir.join("matcher");
+    let mut command = match backend {
+        Backend::RustMatch | Backend::RustTable => {
+            std::fs::write(dir.join("generated.rs"), code).unwrap();
+            std::fs::write(dir.join("main.rs"), RUST_HARNESS).unwrap();
+            let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+            let mut command = Command::new(rustc);
+            command.args(["--edition", "2021", "-o"]).arg(&binary).arg(dir.join("main.rs"));
+            Some(command)
+        }
+        Backend::CSwitch | Backend::CTable => {
+            if !available("cc") {
+                return None;
+            }
+            std::fs::write(dir.join("generated.c"), code).unwrap();
+            std::fs::write(dir.join("main.c"), C_HARNESS).unwrap();
+            let mut command = Command::new("cc");
+            command.args(["-std=c99", "-Wall", "-Wextra", "-Werror", "-o"]).arg(&binary).arg(dir.join("main.c"));
+            Some(command)
+        }
+        Backend::JavaScript => {
+            if !available("node") {
+                return None;
+            }
+            std::fs::write(dir.join("generated.mjs"), code).unwrap();
+            std::fs::write(dir.join("main.mjs"), JS_HARNESS).unwrap();
+            None
+        }
+        // Brak kompilatora TypeScriptu w środowisku testów
+        Backend::TypeScript => return None,
+    };
+    if let Some(command) = &mut command {
+        let output = command.output().unwrap();
+        assert!(output.status.success(), "{}\n{}", String::from_utf8_lossy(&output.stderr), code);
+    }
+
+    let mut run = if backend == Backend::JavaScript {
+        let mut run = Command::new("node");
+        run.arg(dir.join("main.mjs"));
+        run
+    } else {
+        Command::new(&binary)
+    };
+    let mut child = run.stdin(Stdio::piped()).stdout(Stdio::piped()).spawn().unwrap();
+    let input: String = words.iter().map(|w| format!("{}\n", w)).collect();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    Some(String::from_utf8(output.stdout).unwrap().lines().map(|l| l == "1").collect())
+}
+
+fn assert_agrees(name: &str, das: &DAS) {
+    let words = sample_words(das);
+    for backend in Backend::ALL {
+        let code = backend.generate(das).unwrap();
+        let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("codegen_{}_{}", name, backend.name()));
+        let Some(results) = run_generated(backend, &dir, &code, &words) else {
+            continue;
+        };
+        assert_eq!(results.len(), words.len(), "{:?}", backend);
+        for (word, result) in words.iter().zip(results) {
+            assert_eq!(das.process(word), result, "{:?}, słowo {:?}", backend, word);
+        }
+    }
+}
+
+#[test]
+fn binary_multiples_of_three() {
+    let das = build_das(
+        &['0', '1'],
+        &[
+            ("r0", true, &[('0', "r0"), ('1', "r1")]),
+            ("r1", false, &[('0', "r2"), ('1', "r0")]),
+            ("r2", false, &[('0', "r1"), ('1', "r2")]),
+        ],
+        "r0",
+    );
+    assert_agrees("multiples_of_three", &das);
+}
+
+#[test]
+fn partial_automaton_with_unusual_names() {
+    let das = build_das(
+        &['a', '\'', '\\', 'ż'],
+        &[
+            ("start", false, &[('a', "1st"), ('\'', "{q0,q1}")]),
+            ("1st", true, &[('\\', "stan-pułapka"), ('a', "Q0Q1")]),
+            ("{q0,q1}", true, &[('ż', "start")]),
+            ("Q0Q1", false, &[('a', "1st")]),
+            ("stan-pułapka", false, &[]),
+        ],
+        "start",
+    );
+    assert_agrees("unusual_names", &das);
+}
+
+#[test]
+fn automaton_without_accepting_states() {
+    let das = build_das(&['x'], &[("q", false, &[])], "q");
+    assert_agrees("no_accepting", &das);
+}
+
+#[test]
+fn determinized_regex() {
+    let das = ENAS::from_regex("[a-c]*(ab|ba)+c?").unwrap().determinize();
+    assert_agrees("regex", &das);
+}
+
+#[test]
+fn empty_alphabet() {
+    let das = build_das(&[], &[("q", true, &[])], "q");
+    assert_agrees("empty_alphabet", &das);
+}
+
+#[test]
+fn colliding_state_names() {
+    let das = build_das(
+        &['a'],
+        &[("dead", false, &[('a', "DEAD")]), ("DEAD", true, &[('a', "dead")]), ("q-0", false, &[]), ("q_0", true, &[('a', "self")]), ("self", true, &[('a', "Self")]), ("Self", false, &[])],
+        "dead",
+    );
+    assert_agrees("colliding_names", &das);
+}
+
+#[test]
+fn rejects_invalid_automaton() {
+    let das = build_das(&['a'], &[("q", true, &[('a', "missing")])], "q");
+    let missing_start = build_das(&['a'], &[("q", true, &[])], "p");
+    for backend in Backend::ALL {
+        assert!(backend.generate(&das).is_err());
+        assert!(backend.generate(&missing_start).is_err());
+    }
+}